@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
 use std::io;
 use std::io::Write;
+use std::process::ExitCode;
 
-use scheduler::{Days, Shifts, Employee, Scheduler};
+use scheduler::{solver_by_name, Assignment, Calendar, Contract, Date, Days, RestRule, SkillRequirement, StaffingDemand, Shifts, Employee, Holiday, Schedule, ScheduleConfig, Scheduler, SchedulerError, TieBreak, TimeOff, validate, Budget, Improvement, Undesirable, Weights, SOLVER_NAMES};
 
 fn read_input(prompt: &str) -> String {
     print!("{}", prompt);
@@ -13,12 +17,369 @@ fn read_input(prompt: &str) -> String {
     buffer.trim().into()
 }
 
-fn main() {
+fn read_number(prompt: &str, default: usize) -> usize {
+    let input = read_input(&format!("{} [{}]: ", prompt, default));
+    if input.is_empty() {
+        return default;
+    }
 
-    println!("Employee scheduler!");
-    
-    // Store employees
+    input.parse().unwrap_or_else(|_| {
+        println!("Invalid number! Using {}", default);
+        default
+    })
+}
+
+fn read_optional_number(prompt: &str) -> Option<usize> {
+    let input = read_input(&format!("{} [none]: ", prompt));
+    if input.is_empty() {
+        return None;
+    }
+
+    input.parse().map(Some).unwrap_or_else(|_| {
+        println!("Invalid number! Using none");
+        None
+    })
+}
+
+/// Finds the shift whose name starts with `input`, `None` if there is not exactly one.
+fn parse_shift(input: &str, shifts: &[Shifts]) -> Option<Shifts> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+
+    let mut matches = shifts.iter().filter(|shift| shift.name.to_lowercase().starts_with(&input));
+    match (matches.next(), matches.next()) {
+        (Some(shift), None) => Some(shift.clone()),
+        _ => shifts.iter().find(|shift| shift.name.to_lowercase() == input).cloned(),
+    }
+}
+
+fn parse_day(input: &str) -> Option<Days> {
+    let input = input.trim().to_lowercase();
+    if input.len() < 3 {
+        return None;
+    }
+    Days::iter().find(|day| day.to_string().to_lowercase().starts_with(&input))
+}
+
+fn read_shift_definitions(prompt: &str) -> Vec<Shifts> {
+    let input = read_input(prompt);
+
+    let mut shifts: Vec<Shifts> = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        match Shifts::parse(part) {
+            Some(shift) if !shifts.iter().any(|s| s.name.eq_ignore_ascii_case(&shift.name)) => shifts.push(shift),
+            _ => println!("Invalid shift '{}'! Ignoring", part.trim()),
+        }
+    }
+
+    if shifts.is_empty() {
+        Shifts::standard()
+    } else {
+        shifts
+    }
+}
+
+/// Reads shifts by name, or by initials written together such as `MA`.
+fn read_shifts(prompt: &str, available: &[Shifts]) -> Vec<Shifts> {
+    let input = read_input(prompt);
+
+    let mut shifts = Vec::new();
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()) {
+        let parsed = parse_shift(part, available).map(|shift| vec![shift]).or_else(|| {
+            part.chars().map(|c| parse_shift(&c.to_string(), available)).collect()
+        });
+
+        let Some(parsed) = parsed else {
+            println!("Invalid shift '{}'! Ignoring", part);
+            continue;
+        };
+        for shift in parsed {
+            if !shifts.contains(&shift) {
+                shifts.push(shift);
+            }
+        }
+    }
+    shifts
+}
+
+fn read_days(prompt: &str) -> Vec<Days> {
+    let input = read_input(prompt);
+
+    let mut days = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        match parse_day(part) {
+            Some(day) if !days.contains(&day) => days.push(day),
+            Some(_) => {},
+            None => println!("Invalid day '{}'! Ignoring", part.trim()),
+        }
+    }
+    days
+}
+
+fn read_time_off(prompt: &str, shifts: &[Shifts]) -> Vec<TimeOff> {
+    let input = read_input(prompt);
+
+    let mut time_off = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let (day, shift) = match part.split_once(':') {
+            Some((day, shift)) => match parse_shift(shift, shifts) {
+                Some(shift) => (day, Some(shift)),
+                None => {
+                    println!("Invalid time off '{}'! Ignoring", part.trim());
+                    continue;
+                }
+            },
+            None => (part, None),
+        };
+
+        match parse_day(day) {
+            Some(day) => time_off.push(TimeOff { day, shift }),
+            None => println!("Invalid time off '{}'! Ignoring", part.trim()),
+        }
+    }
+    time_off
+}
+
+/// Reads an employee's shifts as `Day:Shift` for every week, or `Date:Shift` for one date.
+fn read_assignments(prompt: &str, employee: &str, shifts: &[Shifts]) -> Vec<Assignment> {
+    let input = read_input(prompt);
+
+    let mut assignments = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let parsed = part.split_once(':').and_then(|(when, shift)| {
+            let shift = parse_shift(shift, shifts)?;
+            match Date::parse(when) {
+                Some(date) => Some(Assignment::on(employee, date, shift)),
+                None => Some(Assignment::new(employee, parse_day(when)?, shift)),
+            }
+        });
+
+        match parsed {
+            Some(assignment) => assignments.push(assignment),
+            None => println!("Invalid shift '{}'! Ignoring", part.trim()),
+        }
+    }
+    assignments
+}
+
+fn read_demand(prompt: &str, shifts: &[Shifts]) -> HashMap<(Days, Shifts), StaffingDemand> {
+    let input = read_input(prompt);
+
+    let mut demand = HashMap::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let parsed = part.split_once('=').and_then(|(slot, count)| {
+            let (day, shift) = slot.split_once(':')?;
+            let shift = parse_shift(shift, shifts)?;
+
+            let (min, max) = match count.split_once('-') {
+                Some((min, max)) => (min.trim().parse().ok()?, Some(max.trim().parse().ok()?)),
+                None => (count.trim().parse().ok()?, None),
+            };
+            Some(((parse_day(day)?, shift), StaffingDemand { min, max }))
+        });
+
+        match parsed {
+            Some((slot, staffing)) => {
+                demand.insert(slot, staffing);
+            },
+            None => println!("Invalid staffing '{}'! Ignoring", part.trim()),
+        }
+    }
+    demand
+}
+
+fn read_skills(prompt: &str) -> Vec<String> {
+    let input = read_input(prompt);
+
+    let mut skills: Vec<String> = Vec::new();
+    for skill in input.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+        if !skills.contains(&skill) {
+            skills.push(skill);
+        }
+    }
+    skills
+}
+
+fn read_skill_requirements(prompt: &str) -> Vec<SkillRequirement> {
+    let input = read_input(prompt);
+
+    let mut requirements = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let parsed = part.split_once('=').and_then(|(skill, count)| {
+            let skill = skill.trim().to_lowercase();
+            if skill.is_empty() {
+                return None;
+            }
+            Some(SkillRequirement::new(&skill, count.trim().parse().ok()?))
+        });
+
+        match parsed {
+            Some(required) => requirements.push(required),
+            None => println!("Invalid skill '{}'! Ignoring", part.trim()),
+        }
+    }
+    requirements
+}
+
+/// Reads groups of shifts to share out evenly, each written as days, shifts or `Days:Shifts`
+/// with several separated by `/`, for example `Sat/Sun,E,Fri:E`, or `none`.
+fn read_undesirable(prompt: &str, shifts: &[Shifts]) -> Vec<Undesirable> {
+    let input = read_input(prompt);
+    if input.trim().is_empty() || input.trim().eq_ignore_ascii_case("none") {
+        return Vec::new();
+    }
+
+    let parse_days = |input: &str| input.split('/').map(parse_day).collect::<Option<Vec<_>>>();
+    let parse_shifts = |input: &str| input.split('/').map(|s| parse_shift(s, shifts)).collect::<Option<Vec<_>>>();
+
+    let mut groups = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let parsed = match part.split_once(':') {
+            Some((days, shifts)) => parse_days(days).zip(parse_shifts(shifts))
+                .map(|(days, shifts)| Undesirable { days, shifts }),
+            None => parse_days(part).map(|days| Undesirable { days, shifts: Vec::new() })
+                .or_else(|| parse_shifts(part).map(|shifts| Undesirable { days: Vec::new(), shifts })),
+        };
+
+        match parsed {
+            Some(group) => groups.push(group),
+            None => println!("Invalid shifts '{}'! Ignoring", part.trim()),
+        }
+    }
+    groups
+}
+
+/// Reads goal weights as `Goal=Weight`, for example `preference=3,undesirable=2`, keeping
+/// the default weight of goals left out.
+fn read_weights(prompt: &str) -> Weights {
+    let input = read_input(prompt);
+
+    let mut weights = Weights::default();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        let parsed = part.split_once('=').and_then(|(goal, weight)| {
+            let weight = weight.trim().parse().ok()?;
+            match goal.trim().to_lowercase().as_str() {
+                "preference" => weights.preference = weight,
+                "fairness" => weights.fairness = weight,
+                "undesirable" => weights.undesirable = weight,
+                _ => return None,
+            }
+            Some(())
+        });
+
+        if parsed.is_none() {
+            println!("Invalid weight '{}'! Ignoring", part.trim());
+        }
+    }
+    weights
+}
+
+/// Reads a seniority that fits in a `u32`, `0` when left blank.
+fn read_seniority(prompt: &str) -> u32 {
+    loop {
+        let input = read_input(&format!("{} [0]: ", prompt));
+        if input.is_empty() {
+            return 0;
+        }
+
+        match input.parse() {
+            Ok(seniority) => return seniority,
+            Err(_) => println!("Invalid seniority! Enter a number up to {}", u32::MAX),
+        }
+    }
+}
+
+/// Reads contract hours as `Min-Max`, a maximum on its own, or `full`/`part` time.
+fn read_contract(prompt: &str) -> Contract {
+    let input = read_input(prompt).to_lowercase();
+
+    let contract = match input.as_str() {
+        "" => Some(Contract::default()),
+        "full" => Some(Contract::full_time()),
+        "part" => Some(Contract::part_time()),
+        _ => match input.split_once('-') {
+            Some((min, max)) => min.trim().parse().ok().zip(max.trim().parse().ok())
+                .map(|(min_hours, max)| Contract { min_hours, max_hours: Some(max) }),
+            None => input.parse().ok().map(|max| Contract { min_hours: 0, max_hours: Some(max) }),
+        },
+    };
+
+    contract.unwrap_or_else(|| {
+        println!("Invalid contract! Using any hours");
+        Contract::default()
+    })
+}
+
+/// Reads a range of dates written as `Start..End`, `None` for a single week.
+fn read_date_range(prompt: &str) -> Option<(Date, Date)> {
+    loop {
+        let input = read_input(prompt);
+        if input.is_empty() {
+            return None;
+        }
+
+        let range = input.split_once("..").and_then(|(start, end)| Some((Date::parse(start)?, Date::parse(end)?)));
+        match range {
+            Some((start, end)) if start <= end => return Some((start, end)),
+            _ => println!("Invalid date range!"),
+        }
+    }
+}
+
+/// Reads holidays from a CSV or iCalendar file, empty for none.
+fn read_calendar(prompt: &str) -> Calendar {
+    loop {
+        let path = read_input(prompt);
+        if path.is_empty() {
+            return Calendar::new();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => match Calendar::parse(&contents) {
+                Ok(calendar) => return calendar,
+                Err(e) => println!("{}!", e),
+            },
+            Err(e) => println!("Could not read '{}': {}!", path, e),
+        }
+    }
+}
+
+fn print_shifts<'a>(shifts: &[Shifts], assignments: impl Fn(&Shifts) -> &'a [Employee]) {
+    for shift in shifts {
+        print!("{:<24}: ", format!("{} {}-{}", shift, shift.start, shift.end));
+
+        let names: Vec<String> = assignments(shift).iter().map(|w| w.name.clone()).collect();
+        println!("{}", names.join(", "));
+    }
+}
+
+fn exit_code(error: &SchedulerError) -> ExitCode {
+    match error {
+        SchedulerError::InWeek { error, .. } => exit_code(error),
+        SchedulerError::InsufficientCapacity { .. } => ExitCode::from(2),
+        SchedulerError::ShiftUnderstaffed { .. } => ExitCode::from(3),
+        SchedulerError::Infeasible => ExitCode::from(4),
+        SchedulerError::TimeOffConflict { .. } => ExitCode::from(5),
+        SchedulerError::ContractHoursUnmet { .. } => ExitCode::from(6),
+        SchedulerError::MissingSkill { .. } => ExitCode::from(7),
+        SchedulerError::PinConflict { .. } => ExitCode::from(8),
+        SchedulerError::SearchLimitReached { .. } => ExitCode::from(10),
+        SchedulerError::InvalidDemand { .. } => ExitCode::from(11),
+    }
+}
+
+/// Reads employees until 'run' is entered, with their pinned and forbidden shifts, asking
+/// for their seniority when ties are broken by it.
+fn read_employees(shifts: &[Shifts], tie_break: TieBreak) -> (Vec<Employee>, Vec<Assignment>, Vec<Assignment>) {
+    let shift_names: Vec<_> = shifts.iter().map(|s| s.name.as_str()).collect();
+    let shift_names = shift_names.join("/");
+
+    // Store employees and their pinned and forbidden shifts
     let mut employees: Vec<Employee> = Vec::new();
+    let mut pinned = Vec::new();
+    let mut forbidden = Vec::new();
 
     // Read employees
     println!("Enter employees and shift preferences");
@@ -32,45 +393,232 @@ fn main() {
             continue;
         }
 
-        let preferred_shift = read_input("Enter Preferred Shift (M/A/E): ");
-
-        let preferred_shift = match preferred_shift.to_lowercase().as_str() {
-            "m" => Shifts::Morning,
-            "a" => Shifts::Afternoon,
-            "e" => Shifts::Evening,
-            _ => {
-                println!("Invalid shift! Setting as Morning");
-                Shifts::Morning
-            }
+        let preferences = read_shifts(&format!("Enter Shift Preferences in order, e.g. MA ({}): ", shift_names), shifts);
+        let unavailable = read_shifts(&format!("Enter Unavailable Shifts, blank for none ({}): ", shift_names), shifts);
+        let unavailable_days = read_days("Enter Unavailable Days, e.g. Mon,Sat, blank for none: ");
+        let time_off = read_time_off("Enter Time Off, e.g. Tue,Fri:E, blank for none: ", shifts);
+        let contract = read_contract("Enter Contract Hours, e.g. 20-40, 40, full or part, blank for any: ");
+        let skills = read_skills("Enter Skills, e.g. keyholder,register, blank for none: ");
+        pinned.extend(read_assignments("Enter Pinned Shifts, e.g. Fri:E or 2026-11-06:E, blank for none: ", &name, shifts));
+        forbidden.extend(read_assignments("Enter Forbidden Shifts, e.g. Sat:M, blank for none: ", &name, shifts));
+        let seniority = match tie_break {
+            TieBreak::Seniority => read_seniority("Enter Seniority, higher favoured in ties"),
+            _ => 0,
         };
 
-        employees.push(Employee { name, preferred_shift });
+        employees.push(Employee { name, preferences, unavailable, unavailable_days, time_off, contract, skills, seniority });
     }
 
+    (employees, pinned, forbidden)
+}
+
+/// Reads a schedule written as lines of `Day,Shift,Name;Name`, or the line number of
+/// the first invalid line.
+///
+/// Names not on the roster are kept so they can be reported.
+fn parse_schedule(input: &str, employees: &[Employee], shifts: &[Shifts]) -> Result<Schedule, usize> {
+    let mut schedule = Schedule::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (index == 0 && line.to_lowercase().starts_with("day,")) {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ',');
+        let day = fields.next().and_then(parse_day).ok_or(index + 1)?;
+        let shift = fields.next().and_then(|shift| parse_shift(shift, shifts)).ok_or(index + 1)?;
+        let assigned = fields.next().unwrap_or_default().split(';')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| employees.iter().find(|e| e.name == name).cloned()
+                .unwrap_or_else(|| Employee { name: name.into(), ..Default::default() }))
+            .collect();
+        schedule.insert(day, shift, assigned);
+    }
+    Ok(schedule)
+}
+
+/// Checks a schedule file against the rules, printing every violation.
+fn validate_schedule(employees: &[Employee], config: &ScheduleConfig, path: Option<String>) -> ExitCode {
+    let path = path.unwrap_or_else(|| read_input("Enter a schedule file to validate, lines of Day,Shift,Name;Name: "));
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Could not read '{}': {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let schedule = match parse_schedule(&contents, employees, &config.shifts) {
+        Ok(schedule) => schedule,
+        Err(line) => {
+            eprintln!("Invalid schedule on line {}", line);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let violations = validate(&schedule, employees, config);
+    if violations.is_empty() {
+        println!("Schedule follows every rule");
+        return ExitCode::SUCCESS;
+    }
+
+    println!("Schedule breaks {} rules", violations.len());
+    for violation in &violations {
+        println!("- {}", violation);
+    }
+    ExitCode::from(9)
+}
+
+fn main() -> ExitCode {
+
+    println!("Employee scheduler!");
+
+    // `validate` checks an existing schedule instead of creating one
+    let validating = env::args().nth(1).is_some_and(|arg| arg.eq_ignore_ascii_case("validate"));
+
+    // Read shift definitions and scheduling rules
+    let shifts = read_shift_definitions("Enter shifts, e.g. Morning=07:00-15:00,Lunch=10:00-14:00/30, blank for Morning/Afternoon/Evening: ");
+    let defaults = ScheduleConfig::default();
+    let rest_rule = RestRule::no_morning_after_evening();
+    let config = ScheduleConfig {
+        shift_min_emps: read_number("Minimum employees per shift", defaults.shift_min_emps),
+        emp_max_days: read_number("Maximum days per employee", defaults.emp_max_days),
+        rest_rules: if shifts.contains(&rest_rule.after) && shifts.contains(&rest_rule.forbidden)
+            && read_input("Forbid Morning after Evening shifts (y/N): ").eq_ignore_ascii_case("y") {
+            vec![rest_rule]
+        } else {
+            Vec::new()
+        },
+        min_rest_hours: read_optional_number("Minimum hours of rest between shifts"),
+        max_consecutive_days: read_optional_number("Maximum consecutive days per employee"),
+        min_days_off_in_a_row: read_optional_number("Minimum days off in a row per employee"),
+        wrap_weeks: read_input("Repeat the week, Sunday followed by Monday (y/N): ").eq_ignore_ascii_case("y"),
+        demand: read_demand("Enter staffing for specific shifts, e.g. Sat:E=4-5,Mon:M=1, blank for none: ", &shifts),
+        shift_skills: read_skill_requirements("Enter skills needed on every shift, e.g. keyholder=1,register=1, blank for none: "),
+        shifts,
+        ..defaults
+    };
+
+    if validating {
+        let (employees, pinned, forbidden) = read_employees(&config.shifts, config.tie_break);
+        let config = ScheduleConfig { pinned, forbidden, ..config };
+        return validate_schedule(&employees, &config, env::args().nth(2));
+    }
+
+    let solver = read_input(&format!("Solver ({}) [{}]: ", SOLVER_NAMES.join("/"), SOLVER_NAMES[0]));
+    let solver = solver_by_name(&solver).unwrap_or_else(|| {
+        if !solver.is_empty() {
+            println!("Invalid solver! Using {}", SOLVER_NAMES[0]);
+        }
+        solver_by_name(SOLVER_NAMES[0]).expect("Default solver exists")
+    });
+    let tie_breaks: Vec<_> = TieBreak::iter().map(|t| t.to_string()).collect();
+    let tie_break = read_input(&format!("Break ties between employees by ({}) [{}]: ", tie_breaks.join("/"), TieBreak::default()));
+    let tie_break = TieBreak::parse(&tie_break).unwrap_or_else(|| {
+        if !tie_break.is_empty() {
+            println!("Invalid tie break! Using {}", TieBreak::default());
+        }
+        TieBreak::default()
+    });
+    let seed = match tie_break {
+        TieBreak::Random => read_number("Seed, the same seed giving the same schedule", 0) as u64,
+        _ => 0,
+    };
+    let weights = read_weights(&format!("Enter goal weights, e.g. preference=3,undesirable=2, blank for {}: ", Weights::default()));
+    // Only suggest evenings when there is an evening shift to share out
+    let has_evenings = config.shifts.iter().any(|shift| shift.name == Shifts::evening().name);
+    let example = if has_evenings { "Sat/Sun,E,Fri:E" } else { "Sat/Sun" };
+    let undesirable = read_undesirable(&format!("Enter undesirable shifts to share out evenly, e.g. {}, blank for none: ", example), &config.shifts);
+    let config = ScheduleConfig { weights, undesirable, tie_break, seed, ..config };
+    let dates = read_date_range("Enter dates to schedule, e.g. 2026-11-02..2026-11-29, blank for a single week: ");
+    let config = match dates {
+        Some(_) => ScheduleConfig {
+            calendar: read_calendar("Enter a holiday calendar file (CSV or iCalendar), blank for none: "),
+            fairness_weeks: read_number("Earlier weeks counted when sharing out undesirable shifts", 0),
+            ..config
+        },
+        None => config,
+    };
+    // A single week is improved after it is solved
+    let budget = match dates {
+        Some(_) => Budget::Iterations(0),
+        None => Budget::Iterations(read_number("Changes to try when improving the schedule", 10_000)),
+    };
+
+    let (employees, pinned, forbidden) = read_employees(&config.shifts, config.tie_break);
+
     // Scheduler
-    let scheduler = Scheduler { employees };
+    let config = ScheduleConfig { pinned, forbidden, ..config };
+    let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
 
     // Get Schedule
-    let schedule = scheduler.get_schedule();
+    let schedule = match dates {
+        Some((start, end)) => scheduler.get_roster(start, end).map(|roster| {
+            // Print roster
+            println!("Roster calculated");
+            let dates = roster.dates();
+            for date in start.range_to(end) {
+                // Closed dates are listed with their holiday
+                let holiday = config.calendar.get(date);
+                if !dates.contains(&date) && !holiday.is_some_and(Holiday::is_closed) {
+                    continue;
+                }
 
-    match schedule {
-        Ok(schedule) => {
+                match holiday {
+                    Some(holiday) => println!("--- {} {} {} ---", date.weekday(), date, holiday),
+                    None => println!("--- {} {} ---", date.weekday(), date),
+                }
+                if dates.contains(&date) {
+                    print_shifts(&config.shifts, |shift| roster.assignments(date, shift));
+                }
+            }
+
+            // Print hours against each contract, per week
+            for (week, schedule) in roster.weeks() {
+                println!("--- Hours, week of {} ---", week);
+                for emp in &employees {
+                    println!("{:<24}: {:.1}h ({})", emp.name, schedule.hours_for(emp), emp.contract);
+                }
+                println!("Score: {}", schedule.score(&employees, &config));
+            }
+
+            for (week, metrics) in scheduler.roster_metrics(&roster, start, end) {
+                println!("--- Metrics, week of {} ---", week);
+                print!("{}", metrics);
+            }
+        }),
+        None => scheduler.get_best_rotation().map(|(schedule, _)| {
+            let Improvement { schedule, before, after, iterations } = scheduler.improve(&schedule, budget);
             // Print schedule
             println!("Schedule calculated");
-            for day in Days::iter() {
+            for &day in &config.operational_days {
                 println!("--- {} ---", day);
-                for shift in Shifts::iter() {
-                    let workers = &schedule[&day][&shift];
-                    print!("{:<10}: ", format!("{:}", shift));
-                    
-                    let names: Vec<String> = workers.iter().map(|w| w.name.clone()).collect();
-                    println!("{}", names.join(", "));
-                }
+                print_shifts(&config.shifts, |shift| schedule.assignments(day, shift));
             }
-        },
+
+            // Print hours against each contract
+            println!("--- Hours ---");
+            for emp in &employees {
+                println!("{:<24}: {:.1}h ({})", emp.name, schedule.hours_for(emp), emp.contract);
+            }
+            println!("Score before improving: {}", before);
+            println!("Score after {} changes tried: {}", iterations, after);
+
+            println!("--- Metrics ---");
+            print!("{}", schedule.metrics(&employees, &config));
+        }),
+    };
+
+    match schedule {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            // No schedule found
-            println!("Error getting schedule: {}", e);
+            // No schedule found, explain which shift and rules caused it
+            let explanation = match dates {
+                Some((start, end)) => scheduler.explain_roster(&e, start, end),
+                None => scheduler.explain(&e),
+            };
+            eprint!("Error getting schedule: {}", explanation);
+            exit_code(&e)
         }
     }
 
@@ -1,9 +1,40 @@
-use std::collections::HashMap;
+use std::fmt;
 
-use scheduler::{Days, Employee, Scheduler, Shifts};
+use scheduler::{solver_by_name, Assignment, Calendar, Contract, Date, Days, Employee, Explanation, Holiday, Metrics, RestRule, SkillRequirement, GreedySolver, Roster, ScheduleConfig, Scheduler, SchedulerError, Score, Shifts, StaffingDemand, TieBreak, TimeOff, Undesirable, Violation, SOLVER_NAMES};
 
 use egui_extras::{Column, TableBuilder};
 
+/// How the employee being added feels about working a shift.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ShiftChoice {
+    /// Ranked preference, `0` being the first choice.
+    Rank(usize),
+    NoPreference,
+    Unavailable,
+}
+
+impl ShiftChoice {
+
+    fn options(num_shifts: usize) -> impl Iterator<Item = ShiftChoice> {
+        (0..num_shifts).map(Self::Rank).chain([Self::NoPreference, Self::Unavailable])
+    }
+}
+
+impl fmt::Display for ShiftChoice {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rank(rank) => write!(f, "Choice {}", rank + 1),
+            Self::NoPreference => write!(f, "No preference"),
+            Self::Unavailable => write!(f, "Unavailable"),
+        }
+    }
+}
+
+fn default_shift_choices(shifts: &[Shifts]) -> Vec<(Shifts, ShiftChoice)> {
+    shifts.iter().map(|shift| (shift.clone(), ShiftChoice::NoPreference)).collect()
+}
+
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
@@ -11,26 +42,120 @@ pub struct TemplateApp {
     name: String,
 
     #[serde(skip)]
-    preferred_shift: Shifts,
+    shift_choices: Vec<(Shifts, ShiftChoice)>,
+
+    #[serde(skip)]
+    new_shift: String,
+
+    #[serde(skip)]
+    unavailable_days: Vec<Days>,
+
+    #[serde(skip)]
+    time_off: Vec<TimeOff>,
+
+    #[serde(skip)]
+    contract: Contract,
+
+    #[serde(skip)]
+    skills: String,
+
+    #[serde(skip)]
+    new_skill: String,
+
+    #[serde(skip)]
+    seniority: u32,
+
+    /// Day of the undesirable shifts being added, `None` for every day
+    #[serde(skip)]
+    undesirable_day: Option<Days>,
+
+    /// Shift of the undesirable shifts being added, `None` for every shift
+    #[serde(skip)]
+    undesirable_shift: Option<Shifts>,
 
     #[serde(skip)]
     employees: Vec<Employee>,
 
+    #[serde(skip)]
+    config: ScheduleConfig,
+
+    #[serde(skip)]
+    solver_name: String,
+
     #[serde(skip)]
     scheduler_run: bool,
 
     #[serde(skip)]
-    schedule: Result<HashMap<Days, HashMap<Shifts, Vec<Employee>>>, String>,
+    start_date: String,
+
+    #[serde(skip)]
+    end_date: String,
+
+    #[serde(skip)]
+    holiday_date: String,
+
+    #[serde(skip)]
+    holiday: Holiday,
+
+    #[serde(skip)]
+    calendar_path: String,
+
+    #[serde(skip)]
+    calendar_error: Option<String>,
+
+    #[serde(skip)]
+    scheduled_dates: Vec<Date>,
+
+    /// Rules the schedule breaks, by the Monday of the week
+    #[serde(skip)]
+    violations: Vec<(Date, Violation)>,
+
+    /// Score of each week of the schedule
+    #[serde(skip)]
+    scores: Vec<(Date, Score)>,
+
+    /// Metrics of each week of the schedule
+    #[serde(skip)]
+    metrics: Vec<(Date, Metrics)>,
+
+    /// Why the schedule could not be created
+    #[serde(skip)]
+    explanation: Option<Explanation>,
+
+    #[serde(skip)]
+    schedule: Result<Roster, SchedulerError>,
 }
 
 impl Default for TemplateApp {
     fn default() -> Self {
         Self {
             name: "".to_owned(),
-            preferred_shift: Shifts::Morning,
+            shift_choices: default_shift_choices(&Shifts::standard()),
+            new_shift: String::new(),
+            unavailable_days: Vec::new(),
+            time_off: Vec::new(),
+            contract: Contract::default(),
+            skills: String::new(),
+            new_skill: String::new(),
+            seniority: 0,
+            undesirable_day: Some(Days::Saturday),
+            undesirable_shift: None,
             employees: Vec::new(),
+            config: ScheduleConfig::default(),
+            solver_name: SOLVER_NAMES[0].to_owned(),
             scheduler_run: false,
-            schedule: Err("".into())
+            start_date: String::new(),
+            end_date: String::new(),
+            holiday_date: String::new(),
+            holiday: Holiday::closed(""),
+            calendar_path: String::new(),
+            calendar_error: None,
+            scheduled_dates: Vec::new(),
+            violations: Vec::new(),
+            scores: Vec::new(),
+            metrics: Vec::new(),
+            explanation: None,
+            schedule: Ok(Roster::new())
         }
     }
 }
@@ -49,6 +174,24 @@ impl TemplateApp {
             Default::default()
         }
     }
+
+    /// Removes a shift along with the staffing, rules, pins and undesirable groups naming it.
+    fn remove_shift(&mut self, shift: &Shifts) {
+        self.config.shifts.retain(|s| s != shift);
+        self.config.demand.retain(|(_, s), _| s != shift);
+        self.config.skill_demand.retain(|(_, s), _| s != shift);
+        self.config.rest_rules.retain(|rule| &rule.after != shift && &rule.forbidden != shift);
+        self.config.pinned.retain(|pin| &pin.shift != shift);
+        self.config.forbidden.retain(|f| &f.shift != shift);
+        // Groups of only the removed shift would otherwise cover every shift
+        self.config.undesirable.retain(|group| group.shifts != [shift.clone()]);
+        for group in &mut self.config.undesirable {
+            group.shifts.retain(|s| s != shift);
+        }
+        if self.undesirable_shift.as_ref() == Some(shift) {
+            self.undesirable_shift = None;
+        }
+    }
 }
 
 impl eframe::App for TemplateApp {
@@ -87,29 +230,106 @@ impl eframe::App for TemplateApp {
 
             ui.separator();
 
+            // Keep the preference choices in step with the shifts being scheduled
+            if !self.shift_choices.iter().map(|(shift, _)| shift).eq(&self.config.shifts) {
+                self.shift_choices = default_shift_choices(&self.config.shifts);
+            }
+
             ui.horizontal(|ui| {
                 ui.label("Name: ");
                 ui.text_edit_singleline(&mut self.name);
             });
 
+            let num_shifts = self.shift_choices.len();
+            for (shift, choice) in self.shift_choices.iter_mut() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}: ", shift));
+                    egui::ComboBox::from_id_salt(format!("shift_preference_cb_{}", shift))
+                        .selected_text(format!("{}", choice))
+                        .show_ui(ui, |ui| {
+                           for option in ShiftChoice::options(num_shifts) {
+                               ui.selectable_value(choice, option, format!("{}", option));
+                           }
+                        });
+                });
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Unavailable days: ");
+                for day in Days::iter() {
+                    let mut unavailable = self.unavailable_days.contains(&day);
+                    if ui.checkbox(&mut unavailable, format!("{}", day)).changed() {
+                        toggle(&mut self.unavailable_days, day, unavailable);
+                    }
+                }
+            });
+
+            ui.collapsing("Time Off", |ui| {
+                egui::Grid::new("time_off_grid").show(ui, |ui| {
+                    ui.label("");
+                    ui.label("Whole day");
+                    for shift in &self.config.shifts {
+                        ui.label(format!("{}", shift));
+                    }
+                    ui.end_row();
+
+                    for day in Days::iter() {
+                        ui.label(format!("{}", day));
+                        for shift in std::iter::once(None).chain(self.config.shifts.iter().cloned().map(Some)) {
+                            let entry = TimeOff { day, shift };
+                            let mut off = self.time_off.contains(&entry);
+                            if ui.checkbox(&mut off, "").changed() {
+                                toggle(&mut self.time_off, entry, off);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
             ui.horizontal(|ui| {
-                ui.label("Shift Preference: ");
-                egui::ComboBox::from_id_salt("shift_preference_cb")
-                    .selected_text(format!("{}", self.preferred_shift))
-                    .show_ui(ui, |ui| {
-                       for shift in Shifts::iter() {
-                           ui.selectable_value(&mut self.preferred_shift, shift, format!("{}", shift));
-                       } 
-                    });
-                // ui.label(format!("{}", self.preferred_shift));
+                ui.label("Contract hours, minimum: ");
+                ui.add(egui::DragValue::new(&mut self.contract.min_hours).range(0..=60));
+                optional_number(ui, "maximum: ", &mut self.contract.max_hours, 1..=60);
             });
 
-            if ui.button("Add Employee").clicked() {
-                if !self.name.is_empty() {
-                    self.employees.push(Employee { name: self.name.clone(), preferred_shift: self.preferred_shift} );
-                    self.name = "".into();
-                    self.preferred_shift = Shifts::Morning;
-                }
+            ui.horizontal(|ui| {
+                ui.label("Skills: ");
+                ui.add(egui::TextEdit::singleline(&mut self.skills).hint_text("keyholder, register"));
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Seniority: ");
+                ui.add(egui::DragValue::new(&mut self.seniority));
+            });
+
+            if ui.button("Add Employee").clicked() && !self.name.is_empty() {
+                let mut ranked: Vec<_> = self.shift_choices.iter()
+                    .filter_map(|(shift, choice)| match choice {
+                        ShiftChoice::Rank(rank) => Some((*rank, shift.clone())),
+                        _ => None,
+                    })
+                    .collect();
+                ranked.sort_by_key(|&(rank, _)| rank);
+
+                self.employees.push(Employee {
+                    name: self.name.clone(),
+                    preferences: ranked.into_iter().map(|(_, shift)| shift).collect(),
+                    unavailable: self.shift_choices.iter()
+                        .filter(|(_, choice)| *choice == ShiftChoice::Unavailable)
+                        .map(|(shift, _)| shift.clone())
+                        .collect(),
+                    unavailable_days: std::mem::take(&mut self.unavailable_days),
+                    time_off: std::mem::take(&mut self.time_off),
+                    contract: std::mem::take(&mut self.contract),
+                    skills: std::mem::take(&mut self.skills).split(',')
+                        .map(|skill| skill.trim().to_lowercase())
+                        .filter(|skill| !skill.is_empty())
+                        .collect(),
+                    seniority: std::mem::take(&mut self.seniority),
+                });
+                self.name = "".into();
+                self.shift_choices = default_shift_choices(&self.config.shifts);
             }
 
             ui.separator();
@@ -122,96 +342,482 @@ impl eframe::App for TemplateApp {
 
             });
 
+            ui.separator();
+            ui.collapsing("Shifts", |ui| {
+                let mut removed = None;
+                egui::Grid::new("shifts_grid").show(ui, |ui| {
+                    ui.label("Shift");
+                    ui.label("Start");
+                    ui.label("End");
+                    ui.label("Break");
+                    ui.label("Hours");
+                    ui.end_row();
+
+                    for (i, shift) in self.config.shifts.iter().enumerate() {
+                        ui.label(&shift.name);
+                        ui.label(shift.start.to_string());
+                        ui.label(shift.end.to_string());
+                        ui.label(format!("{} min", shift.break_minutes));
+                        ui.label(format!("{:.1}", shift.hours()));
+                        if ui.button("Remove").clicked() {
+                            removed = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = removed {
+                    let shift = self.config.shifts[i].clone();
+                    self.remove_shift(&shift);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.new_shift).hint_text("Lunch=10:00-14:00/30"));
+                    let parsed = Shifts::parse(&self.new_shift)
+                        .filter(|shift| !self.config.shifts.iter().any(|s| s.name == shift.name));
+                    if ui.add_enabled(parsed.is_some(), egui::Button::new("Add Shift")).clicked() {
+                        self.config.shifts.extend(parsed);
+                        self.config.shifts.sort();
+                        self.new_shift.clear();
+                    }
+                    if ui.button("Reset Shifts").clicked() {
+                        let standard = Shifts::standard();
+                        for shift in self.config.shifts.clone() {
+                            if !standard.contains(&shift) {
+                                self.remove_shift(&shift);
+                            }
+                        }
+                        self.config.shifts = standard;
+                        self.config.demand.clear();
+                        self.config.rest_rules.clear();
+                    }
+                });
+            });
 
-            /*
-            ui.scope(|ui| {
-                ui.set_max_size([400., 200.].into());
+            ui.collapsing("Rules", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Minimum employees per shift: ");
+                    ui.add(egui::DragValue::new(&mut self.config.shift_min_emps).range(1..=10));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Maximum employees per shift: ");
+                    let mut max = self.config.shift_max_emps.unwrap_or(0);
+                    if ui.add(max_drag_value(&mut max)).changed() {
+                        self.config.shift_max_emps = (max > 0).then_some(max);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Maximum days per employee: ");
+                    ui.add(egui::DragValue::new(&mut self.config.emp_max_days).range(1..=7));
+                });
+                optional_number(ui, "Maximum consecutive days: ", &mut self.config.max_consecutive_days, 1..=7);
+                optional_number(ui, "Minimum days off in a row: ", &mut self.config.min_days_off_in_a_row, 1..=7);
+                optional_number(ui, "Minimum hours of rest between shifts: ", &mut self.config.min_rest_hours, 1..=24);
+                ui.checkbox(&mut self.config.wrap_weeks, "Repeat the week, Sunday followed by Monday");
+                ui.label("Shifts not allowed the day after a shift: ");
+                egui::Grid::new("rest_rules_grid").show(ui, |ui| {
+                    ui.label("After / Next day");
+                    for shift in &self.config.shifts {
+                        ui.label(format!("{}", shift));
+                    }
+                    ui.end_row();
 
-                let mut tb = TableBuilder::new(ui);
+                    for after in &self.config.shifts {
+                        ui.label(format!("{}", after));
+                        for forbidden in &self.config.shifts {
+                            let rule = RestRule { after: after.clone(), forbidden: forbidden.clone() };
+                            let mut on = self.config.rest_rules.contains(&rule);
+                            if ui.checkbox(&mut on, "").changed() {
+                                toggle(&mut self.config.rest_rules, rule, on);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.label("Skills needed on every shift: ");
+                let mut removed = None;
+                egui::Grid::new("shift_skills_grid").show(ui, |ui| {
+                    for (i, required) in self.config.shift_skills.iter_mut().enumerate() {
+                        ui.label(&required.skill);
+                        ui.add(egui::DragValue::new(&mut required.count).range(1..=10));
+                        if ui.button("Remove").clicked() {
+                            removed = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = removed {
+                    self.config.shift_skills.remove(i);
+                }
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.new_skill).hint_text("keyholder"));
+                    let skill = self.new_skill.trim().to_lowercase();
+                    let valid = !skill.is_empty() && !self.config.shift_skills.iter().any(|r| r.skill == skill);
+                    if ui.add_enabled(valid, egui::Button::new("Add Skill")).clicked() {
+                        self.config.shift_skills.push(SkillRequirement::new(&skill, 1));
+                        self.new_skill.clear();
+                    }
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Operational days: ");
+                    for day in Days::iter() {
+                        let mut open = self.config.operational_days.contains(&day);
+                        if ui.checkbox(&mut open, format!("{}", day)).changed() {
+                            self.config.operational_days = Days::iter()
+                                .filter(|d| if *d == day { open } else { self.config.operational_days.contains(d) })
+                                .collect();
+                        }
+                    }
+                });
+            });
 
-                tb = tb.column(Column::auto());
-                tb = tb.column(Column::auto());
+            ui.collapsing("Goals", |ui| {
+                ui.label("How much each goal counts when scoring the schedule, 0 to ignore it");
+                let weights = &mut self.config.weights;
+                egui::Grid::new("weights_grid").show(ui, |ui| {
+                    for (label, weight) in [
+                        ("Preferred shifts", &mut weights.preference),
+                        ("Even days worked", &mut weights.fairness),
+                        ("Even undesirable shifts", &mut weights.undesirable),
+                    ] {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(weight).range(0..=10));
+                        ui.end_row();
+                    }
+                });
 
-                tb.header(20., |mut header| {
-                    header.col(|ui| {
-                       ui.heading("Name");
-                    });
-                    header.col(|ui| {
-                       ui.heading("Preferred Shift");
+                ui.label("Undesirable shifts, shared out evenly between employees");
+                let mut removed = None;
+                for (index, group) in self.config.undesirable.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(group.to_string());
+                        if ui.button("Remove").clicked() {
+                            removed = Some(index);
+                        }
                     });
-                })
-                .body(|mut body| {
-                    for emp in &self.employees {
-                        body.row(30., |mut row| {
-                            row.col(|ui| {
-                                ui.label(format!("{}", emp.name));
-                            });
-                            row.col(|ui| {
-                                ui.label(format!("{}", emp.preferred_shift));
-                            });
+                }
+                if let Some(index) = removed {
+                    self.config.undesirable.remove(index);
+                }
+
+                ui.horizontal(|ui| {
+                    let any = |option: Option<String>| option.unwrap_or_else(|| "Any".to_owned());
+                    egui::ComboBox::from_id_salt("undesirable_day_cb")
+                        .selected_text(any(self.undesirable_day.map(|d| d.to_string())))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.undesirable_day, None, "Any");
+                            for day in Days::iter() {
+                                ui.selectable_value(&mut self.undesirable_day, Some(day), day.to_string());
+                            }
                         });
+                    egui::ComboBox::from_id_salt("undesirable_shift_cb")
+                        .selected_text(any(self.undesirable_shift.as_ref().map(|s| s.to_string())))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.undesirable_shift, None, "Any");
+                            for shift in &self.config.shifts {
+                                ui.selectable_value(&mut self.undesirable_shift, Some(shift.clone()), shift.to_string());
+                            }
+                        });
+                    if ui.button("Add").clicked() {
+                        let group = Undesirable {
+                            days: self.undesirable_day.into_iter().collect(),
+                            shifts: self.undesirable_shift.clone().into_iter().collect(),
+                        };
+                        if !self.config.undesirable.contains(&group) {
+                            self.config.undesirable.push(group);
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Earlier weeks counted: ");
+                    ui.add(egui::DragValue::new(&mut self.config.fairness_weeks).range(0..=52));
+                });
+            });
+
+            ui.collapsing("Staffing", |ui| {
+                ui.label("Minimum and maximum employees on each shift, - for no maximum");
+                egui::Grid::new("staffing_grid").show(ui, |ui| {
+                    ui.label("Day / Shift");
+                    for shift in &self.config.shifts {
+                        ui.label(format!("{}", shift));
+                    }
+                    ui.end_row();
+
+                    for day in Days::iter() {
+                        ui.label(format!("{}", day));
+                        for shift in &self.config.shifts {
+                            let mut demand = self.config.demand(day, shift);
+                            let mut max = demand.max.unwrap_or(0);
+                            ui.horizontal(|ui| {
+                                let min_changed = ui.add(egui::DragValue::new(&mut demand.min).range(0..=20)).changed();
+                                let max_changed = ui.add(max_drag_value(&mut max)).changed();
+                                if min_changed || max_changed {
+                                    demand.max = (max > 0).then_some(max);
+                                    self.config.demand.insert((day, shift.clone()), demand);
+                                }
+                            });
+                        }
+                        ui.end_row();
+                    }
+                });
+                if ui.button("Reset Staffing").clicked() {
+                    self.config.demand.clear();
+                }
+            });
+
+            ui.collapsing("Holidays", |ui| {
+                ui.label("Dates that are closed, or staffed differently on every shift");
+                let mut removed = None;
+                egui::Grid::new("holidays_grid").show(ui, |ui| {
+                    for (date, holiday) in self.config.calendar.iter() {
+                        ui.label(format!("{} {}", date.weekday(), date));
+                        ui.label(holiday.to_string());
+                        if ui.button("Remove").clicked() {
+                            removed = Some(date);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(date) = removed {
+                    self.config.calendar.remove(date);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.holiday_date).desired_width(90.).hint_text("2026-12-25"));
+                    ui.add(egui::TextEdit::singleline(&mut self.holiday.name).desired_width(120.).hint_text("Christmas Day"));
+
+                    let mut closed = self.holiday.is_closed();
+                    if ui.checkbox(&mut closed, "Closed").changed() {
+                        // A holiday applies to every shift, so open it with the default staffing
+                        let demand = StaffingDemand { min: self.config.shift_min_emps, max: self.config.shift_max_emps };
+                        self.holiday.demand = if closed { None } else { Some(demand) };
+                    }
+                    if let Some(demand) = &mut self.holiday.demand {
+                        let mut max = demand.max.unwrap_or(0);
+                        ui.add(egui::DragValue::new(&mut demand.min).range(0..=20));
+                        if ui.add(max_drag_value(&mut max)).changed() {
+                            demand.max = (max > 0).then_some(max);
+                        }
+                    }
+
+                    let date = Date::parse(&self.holiday_date);
+                    let valid = date.is_some() && !self.holiday.name.trim().is_empty();
+                    if ui.add_enabled(valid, egui::Button::new("Add Holiday")).clicked()
+                        && let Some(date) = date {
+                        let holiday = Holiday { name: self.holiday.name.trim().to_owned(), demand: self.holiday.demand };
+                        self.config.calendar.insert(date, holiday);
+                        self.holiday_date.clear();
+                        self.holiday.name.clear();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.calendar_path).hint_text("holidays.csv or holidays.ics"));
+                    if ui.add_enabled(!self.calendar_path.trim().is_empty(), egui::Button::new("Load Calendar")).clicked() {
+                        let loaded = std::fs::read_to_string(self.calendar_path.trim())
+                            .map_err(|e| e.to_string())
+                            .and_then(|contents| Calendar::parse(&contents).map_err(|e| e.to_string()));
+                        match loaded {
+                            Ok(calendar) => {
+                                for (date, holiday) in calendar.iter() {
+                                    self.config.calendar.insert(date, holiday.clone());
+                                }
+                                self.calendar_error = None;
+                            },
+                            Err(error) => self.calendar_error = Some(error),
+                        }
+                    }
+                    if ui.button("Clear Holidays").clicked() {
+                        self.config.calendar = Calendar::new();
+                    }
+                });
+                if let Some(error) = &self.calendar_error {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("Could not load calendar: {}", error));
+                }
+            });
+
+            ui.collapsing("Locks", |ui| {
+                ui.label("Employees locked into or kept off shifts, set from the schedule table");
+                let mut removed = None;
+                egui::Grid::new("locks_grid").show(ui, |ui| {
+                    for (forbid, list) in [(false, &self.config.pinned), (true, &self.config.forbidden)] {
+                        for (i, assignment) in list.iter().enumerate() {
+                            ui.label(if forbid { "Kept off" } else { "Locked" });
+                            ui.label(assignment.to_string());
+                            if ui.button("Remove").clicked() {
+                                removed = Some((forbid, i));
+                            }
+                            ui.end_row();
+                        }
                     }
                 });
+                match removed {
+                    Some((false, i)) => { self.config.pinned.remove(i); },
+                    Some((true, i)) => { self.config.forbidden.remove(i); },
+                    None => {},
+                }
+                if ui.button("Clear Locks").clicked() {
+                    self.config.pinned.clear();
+                    self.config.forbidden.clear();
+                }
             });
-            */
 
             ui.separator();
-            if ui.button("Get Schedule").clicked() {
-                let scheduler = Scheduler { employees: self.employees.clone() };
-                self.schedule = scheduler.get_schedule();
+            ui.horizontal(|ui| {
+                ui.label("Solver: ");
+                egui::ComboBox::from_id_salt("solver_cb")
+                    .selected_text(&self.solver_name)
+                    .show_ui(ui, |ui| {
+                        for &name in SOLVER_NAMES {
+                            ui.selectable_value(&mut self.solver_name, name.to_owned(), name);
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Break ties by: ");
+                egui::ComboBox::from_id_salt("tie_break_cb")
+                    .selected_text(self.config.tie_break.to_string())
+                    .show_ui(ui, |ui| {
+                        for tie_break in TieBreak::iter() {
+                            ui.selectable_value(&mut self.config.tie_break, tie_break, tie_break.to_string());
+                        }
+                    });
+                if self.config.tie_break == TieBreak::Random {
+                    ui.label("Seed: ");
+                    ui.add(egui::DragValue::new(&mut self.config.seed));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Dates: ");
+                ui.add(egui::TextEdit::singleline(&mut self.start_date).desired_width(90.).hint_text("2026-11-02"));
+                ui.label("to");
+                ui.add(egui::TextEdit::singleline(&mut self.end_date).desired_width(90.).hint_text("2026-11-08"));
+            });
+
+            let dates = Date::parse(&self.start_date).zip(Date::parse(&self.end_date))
+                .filter(|(start, end)| start <= end);
+            if dates.is_none() {
+                ui.colored_label(ui.visuals().warn_fg_color, "Enter dates as YYYY-MM-DD, the start before the end");
+            }
+
+            if ui.add_enabled(dates.is_some(), egui::Button::new("Get Schedule")).clicked()
+                && let Some((start, end)) = dates {
+                let scheduler = Scheduler {
+                    employees: self.employees.clone(),
+                    config: self.config.clone(),
+                    solver: solver_by_name(&self.solver_name).unwrap_or_else(|| Box::new(GreedySolver)),
+                };
+                self.schedule = scheduler.get_roster(start, end);
+                self.violations = match &self.schedule {
+                    Ok(roster) => scheduler.validate_roster(roster, start, end),
+                    Err(_) => Vec::new(),
+                };
+                self.scores = self.schedule.iter()
+                    .flat_map(|roster| roster.weeks())
+                    .map(|(week, schedule)| (week, schedule.score(&scheduler.employees, &scheduler.config)))
+                    .collect();
+                self.metrics = match &self.schedule {
+                    Ok(roster) => scheduler.roster_metrics(roster, start, end),
+                    Err(_) => Vec::new(),
+                };
+                self.explanation = self.schedule.as_ref().err().map(|error| scheduler.explain_roster(error, start, end));
                 self.scheduler_run = true;
+
+                // Closed holidays get a row of their own
+                self.scheduled_dates = start.range_to(end)
+                    .filter(|&date| self.config.calendar.is_closed(date)
+                        || self.schedule.as_ref().is_ok_and(|roster| roster.dates().contains(&date)))
+                    .collect();
             }
 
             ui.separator();
             ui.heading("Schedule");
 
-            let status = match &self.schedule {
-                Ok(_) => {
-                    "Schedule calculated".into()
-                },
-                Err(error) => {
-                    format!("Error getting schedule: {}", error)
-                }
-            };
-
-            if self.scheduler_run {
-                ui.label(status);
-            } else {
+            if !self.scheduler_run {
                 ui.label("Scheduler not run");
+            } else {
+                match &self.schedule {
+                    Ok(_) if self.violations.is_empty() => {
+                        ui.label("Schedule calculated");
+                    },
+                    Ok(_) => {
+                        ui.colored_label(ui.visuals().warn_fg_color, format!("Schedule calculated, breaking {} rules", self.violations.len()));
+                        ui.collapsing("Broken rules", |ui| {
+                            for (week, violation) in &self.violations {
+                                ui.label(format!("Week of {}: {}", week, violation));
+                            }
+                        });
+                    },
+                    Err(error) => {
+                        ui.colored_label(ui.visuals().error_fg_color, format!("Error getting schedule: {}", error));
+                        show_error_details(ui, error);
+                        if let Some(explanation) = &self.explanation {
+                            show_explanation(ui, explanation);
+                        }
+                    }
+                }
+                for (week, score) in &self.scores {
+                    ui.label(format!("Week of {} scores {}, lower is better", week, score));
+                }
             }
 
-            if self.schedule.is_ok() {
+            if self.scheduler_run && self.schedule.is_ok() {
+                ui.label("Click an employee to lock them into the shift, or right click to keep them off it, then Get Schedule to solve again");
+
+                // Employee clicked in the table, and whether to keep them off the shift
+                let mut clicked: Option<(Assignment, bool)> = None;
                 ui.scope(|ui| {
                     ui.set_max_size([400., 800.].into());
 
                     let mut tb = TableBuilder::new(ui);
 
                     tb = tb.column(Column::auto());
-                    for _ in Shifts::iter() {
+                    for _ in &self.config.shifts {
                         tb = tb.column(Column::auto());
                     }
 
-                    tb.header(20., |mut header| {
+                    tb.header(40., |mut header| {
                         header.col(|ui| {
                            ui.heading("Day / Shift");
                         });
-                        for shift in Shifts::iter() {
+                        for shift in &self.config.shifts {
                             header.col(|ui| {
                                 ui.heading(format!("{}", shift));
+                                ui.label(format!("{}-{}", shift.start, shift.end));
                             });
                         }
                     })
                     .body(|mut body| {
-                        for day in Days::iter() {
-                            body.row(30., |mut row| {
+                        let Ok(res) = &self.schedule else {
+                            return;
+                        };
+                        for &date in &self.scheduled_dates {
+                            let holiday = self.config.calendar.get(date);
+                            body.row(if holiday.is_some() { 40. } else { 30. }, |mut row| {
                                 row.col(|ui| {
-                                    ui.label(format!("{day}"));
+                                    ui.label(format!("{} {}", date.weekday(), date));
+                                    if let Some(holiday) = holiday {
+                                        ui.colored_label(ui.visuals().warn_fg_color, holiday.to_string());
+                                    }
                                 });
-                                for shift in Shifts::iter() {
+                                for shift in &self.config.shifts {
                                     row.col(|ui| {
-                                        if let Ok(res) = &self.schedule {
-                                            for emp in &res[&day][&shift] {
-                                                ui.label(format!("{}", emp.name));
+                                        let broken: Vec<_> = self.violations.iter()
+                                            .filter(|(week, v)| v.day().is_some_and(|day| week.in_week(day) == date) && v.shift() == Some(shift))
+                                            .map(|(_, v)| v.to_string())
+                                            .collect();
+                                        if !broken.is_empty() {
+                                            ui.colored_label(ui.visuals().error_fg_color, "⚠").on_hover_text(broken.join("\n"));
+                                        }
+                                        for emp in res.assignments(date, shift) {
+                                            let assignment = Assignment::on(&emp.name, date, shift.clone());
+                                            let response = ui.selectable_label(self.config.pinned.contains(&assignment), &emp.name);
+                                            if response.clicked() {
+                                                clicked = Some((assignment, false));
+                                            } else if response.secondary_clicked() {
+                                                clicked = Some((assignment, true));
                                             }
                                         }
                                     });
@@ -220,6 +826,46 @@ impl eframe::App for TemplateApp {
                         }
                     });
                 });
+
+                if let Some((assignment, forbid)) = clicked {
+                    let locked = self.config.pinned.contains(&assignment);
+                    self.config.pinned.retain(|pin| pin != &assignment);
+                    if forbid {
+                        if !self.config.forbidden.contains(&assignment) {
+                            self.config.forbidden.push(assignment);
+                        }
+                    } else if !locked {
+                        self.config.pinned.push(assignment);
+                    }
+                }
+
+                if let Ok(roster) = &self.schedule {
+                    ui.collapsing("Hours", |ui| {
+                        egui::Grid::new("hours_grid").show(ui, |ui| {
+                            ui.label("Employee");
+                            ui.label("Contract");
+                            for (week, _) in roster.weeks() {
+                                ui.label(format!("Week of {}", week));
+                            }
+                            ui.end_row();
+
+                            for emp in &self.employees {
+                                ui.label(&emp.name);
+                                ui.label(emp.contract.to_string());
+                                for (_, schedule) in roster.weeks() {
+                                    ui.label(format!("{:.1}h", schedule.hours_for(emp)));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+
+                ui.collapsing("Metrics", |ui| {
+                    for (week, metrics) in &self.metrics {
+                        show_metrics(ui, *week, metrics);
+                    }
+                });
             }
 
 
@@ -233,6 +879,209 @@ impl eframe::App for TemplateApp {
     }
 }
 
+/// Drag value for a maximum number of employees, where `0` means no maximum.
+fn max_drag_value(max: &mut usize) -> egui::DragValue<'_> {
+    egui::DragValue::new(max)
+        .range(0..=20)
+        .custom_formatter(|n, _| if n == 0.0 { "-".into() } else { n.to_string() })
+}
+
+/// Edits an optional rule with a checkbox to turn it on and a value.
+fn optional_number(ui: &mut egui::Ui, label: &str, value: &mut Option<usize>, range: std::ops::RangeInclusive<usize>) {
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        if ui.checkbox(&mut enabled, label).changed() {
+            *value = enabled.then_some(*range.start());
+        }
+        if let Some(value) = value {
+            ui.add(egui::DragValue::new(value).range(range));
+        }
+    });
+}
+
+/// Adds or removes an item from a list.
+fn toggle<T: PartialEq>(list: &mut Vec<T>, item: T, on: bool) {
+    if on {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    } else {
+        list.retain(|i| *i != item);
+    }
+}
+
+/// Shows the fields of a scheduler error as a grid.
+fn show_error_details(ui: &mut egui::Ui, error: &SchedulerError) {
+    egui::Grid::new("schedule_error_grid").show(ui, |ui| {
+        let mut error = error;
+        while let SchedulerError::InWeek { week_of, error: inner } = error {
+            ui.label("Week of:");
+            ui.label(week_of.to_string());
+            ui.end_row();
+            error = inner;
+        }
+
+        match error {
+            SchedulerError::InsufficientCapacity { required, available } => {
+                ui.label("Shifts required:");
+                ui.label(required.to_string());
+                ui.end_row();
+                ui.label("Shifts available:");
+                ui.label(available.to_string());
+                ui.end_row();
+            },
+            SchedulerError::ShiftUnderstaffed { day, shift, needed, found } => {
+                ui.label("Day:");
+                ui.label(day.to_string());
+                ui.end_row();
+                ui.label("Shift:");
+                ui.label(shift.to_string());
+                ui.end_row();
+                ui.label("Employees needed:");
+                ui.label(needed.to_string());
+                ui.end_row();
+                ui.label("Employees found:");
+                ui.label(found.to_string());
+                ui.end_row();
+            },
+            SchedulerError::TimeOffConflict { day, shift, needed, found, unavailable } => {
+                ui.label("Day:");
+                ui.label(day.to_string());
+                ui.end_row();
+                ui.label("Shift:");
+                ui.label(shift.to_string());
+                ui.end_row();
+                ui.label("Employees needed:");
+                ui.label(needed.to_string());
+                ui.end_row();
+                ui.label("Employees available:");
+                ui.label(found.to_string());
+                ui.end_row();
+                ui.label("Unavailable or on time off:");
+                ui.label(unavailable.join(", "));
+                ui.end_row();
+            },
+            SchedulerError::MissingSkill { day, shift, skill, needed, found } => {
+                ui.label("Day:");
+                ui.label(day.to_string());
+                ui.end_row();
+                ui.label("Shift:");
+                ui.label(shift.to_string());
+                ui.end_row();
+                ui.label("Skill:");
+                ui.label(skill);
+                ui.end_row();
+                ui.label("Employees needed:");
+                ui.label(needed.to_string());
+                ui.end_row();
+                ui.label("Employees found:");
+                ui.label(found.to_string());
+                ui.end_row();
+            },
+            SchedulerError::ContractHoursUnmet { employee, min_hours, scheduled_minutes } => {
+                ui.label("Employee:");
+                ui.label(employee);
+                ui.end_row();
+                ui.label("Contracted hours:");
+                ui.label(min_hours.to_string());
+                ui.end_row();
+                ui.label("Hours scheduled:");
+                ui.label(format!("{:.1}", *scheduled_minutes as f32 / 60.0));
+                ui.end_row();
+            },
+            SchedulerError::PinConflict { employee, day, shift } => {
+                ui.label("Employee:");
+                ui.label(employee);
+                ui.end_row();
+                ui.label("Day:");
+                ui.label(day.to_string());
+                ui.end_row();
+                ui.label("Shift:");
+                ui.label(shift.to_string());
+                ui.end_row();
+            },
+            SchedulerError::Infeasible => {
+                ui.label("The employees cannot cover every shift with the current rules.");
+                ui.end_row();
+            },
+            SchedulerError::InvalidDemand { day, shift, min, max } => {
+                ui.label("Day:");
+                ui.label(day.to_string());
+                ui.end_row();
+                ui.label("Shift:");
+                ui.label(shift.to_string());
+                ui.end_row();
+                ui.label("Minimum employees:");
+                ui.label(min.to_string());
+                ui.end_row();
+                ui.label("Maximum employees:");
+                ui.label(max.to_string());
+                ui.end_row();
+            },
+            SchedulerError::SearchLimitReached { tried } => {
+                ui.label("Assignments tried:");
+                ui.label(tried.to_string());
+                ui.end_row();
+            },
+            SchedulerError::InWeek { .. } => {},
+        }
+    });
+}
+
+fn show_metrics(ui: &mut egui::Ui, week: Date, metrics: &Metrics) {
+    ui.strong(format!("Week of {}", week));
+    egui::Grid::new(format!("metrics_grid_{}", week)).show(ui, |ui| {
+        for heading in ["Employee", "Shifts", "Days", "Preferred", "Weekends", "Hours"] {
+            ui.label(heading);
+        }
+        for (group, _) in &metrics.undesirable {
+            ui.label(group.to_string());
+        }
+        ui.end_row();
+
+        for e in &metrics.employees {
+            ui.label(&e.name);
+            ui.label(e.shifts.to_string());
+            ui.label(e.days.to_string());
+            ui.label(format!("{:.0}%", e.preferred_percent()));
+            ui.label(e.weekends.to_string());
+            ui.label(format!("{:.1}h", e.minutes as f32 / 60.0));
+            for worked in &e.undesirable {
+                ui.label(worked.to_string());
+            }
+            ui.end_row();
+        }
+    });
+    ui.label(format!("Assignments on a preferred shift: {:.0}%", metrics.preferred_percent));
+    ui.label(format!("Shifts per employee: {:.1} average, {:.2} standard deviation, {} between most and fewest",
+        metrics.mean_shifts, metrics.shifts_std_dev, metrics.shifts_spread));
+    for (group, spread) in &metrics.undesirable {
+        ui.label(format!("{} shifts: {} between most and fewest", group, spread));
+    }
+    for (day, shift, found) in &metrics.understaffed {
+        ui.colored_label(ui.visuals().error_fg_color, format!("Understaffed: {} {} with {}", day, shift, found));
+    }
+    for (day, shift, found) in &metrics.overstaffed {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("Overstaffed: {} {} with {}", day, shift, found));
+    }
+    ui.separator();
+}
+
+fn show_explanation(ui: &mut egui::Ui, explanation: &Explanation) {
+    if let Some((day, shift)) = &explanation.slot {
+        ui.label(format!("{} {} needs {} employees, {} eligible", day, shift, explanation.needed, explanation.eligible));
+    }
+    for (employee, exclusion) in &explanation.excluded {
+        ui.label(format!("{} is {}", employee, exclusion));
+    }
+    if !explanation.suggestions.is_empty() {
+        ui.label("Suggestions:");
+        for suggestion in &explanation.suggestions {
+            ui.colored_label(ui.visuals().warn_fg_color, suggestion.to_string());
+        }
+    }
+}
+
 fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         ui.spacing_mut().item_spacing.x = 0.0;
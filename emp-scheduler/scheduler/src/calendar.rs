@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{Date, StaffingDemand};
+
+/// A public holiday or other date with unusual opening.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Holiday {
+    pub name: String,
+
+    /// Staffing for every shift on the date, `None` when closed.
+    pub demand: Option<StaffingDemand>,
+}
+
+impl Holiday {
+
+    pub fn closed(name: &str) -> Self {
+        Self { name: name.into(), demand: None }
+    }
+
+    pub fn with_demand(name: &str, demand: StaffingDemand) -> Self {
+        Self { name: name.into(), demand: Some(demand) }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.demand.is_none()
+    }
+}
+
+impl fmt::Display for Holiday {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.demand {
+            None => write!(f, "{} (closed)", self.name),
+            Some(StaffingDemand { min, max: None }) => write!(f, "{} ({} per shift)", self.name, min),
+            Some(StaffingDemand { min, max: Some(max) }) => write!(f, "{} ({}-{} per shift)", self.name, min, max),
+        }
+    }
+}
+
+/// A calendar entry that could not be read, with the line it is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCalendarError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseCalendarError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid calendar entry on line {}", self.line)
+    }
+}
+
+impl Error for ParseCalendarError {}
+
+/// Holidays and closures by date.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Calendar {
+    holidays: BTreeMap<Date, Holiday>,
+}
+
+impl Calendar {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a holiday, replacing any already on the date.
+    pub fn insert(&mut self, date: Date, holiday: Holiday) {
+        self.holidays.insert(date, holiday);
+    }
+
+    pub fn remove(&mut self, date: Date) -> Option<Holiday> {
+        self.holidays.remove(&date)
+    }
+
+    pub fn get(&self, date: Date) -> Option<&Holiday> {
+        self.holidays.get(&date)
+    }
+
+    /// Checks nothing is scheduled on the date.
+    pub fn is_closed(&self, date: Date) -> bool {
+        self.get(date).is_some_and(Holiday::is_closed)
+    }
+
+    /// Holidays in date order.
+    pub fn iter(&self) -> impl Iterator<Item = (Date, &Holiday)> {
+        self.holidays.iter().map(|(date, holiday)| (*date, holiday))
+    }
+
+    /// Holidays from `start` up to and including `end`.
+    pub fn between(&self, start: Date, end: Date) -> impl Iterator<Item = (Date, &Holiday)> {
+        self.holidays.range(start..=end).map(|(date, holiday)| (*date, holiday))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.holidays.is_empty()
+    }
+
+    /// Reads an iCalendar file when it starts with `BEGIN:VCALENDAR`, otherwise CSV.
+    pub fn parse(input: &str) -> Result<Self, ParseCalendarError> {
+        if input.trim_start().starts_with("BEGIN:VCALENDAR") {
+            Self::parse_ical(input)
+        } else {
+            Self::parse_csv(input)
+        }
+    }
+
+    /// Reads lines of `date,name,staffing`, for example `2026-12-24,Christmas Eve,4-6`.
+    ///
+    /// Staffing is `Min-Max` or `Min` employees per shift, and the date is closed when
+    /// it is blank or `closed`. Blank lines, `#` comments and a `date` header are skipped.
+    pub fn parse_csv(input: &str) -> Result<Self, ParseCalendarError> {
+        let mut calendar = Self::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, ',').map(|field| field.trim().trim_matches('"').trim());
+            let date = fields.next().unwrap_or_default();
+            if index == 0 && date.eq_ignore_ascii_case("date") {
+                continue;
+            }
+
+            let error = ParseCalendarError { line: index + 1 };
+            let date = Date::parse(date).ok_or(error.clone())?;
+            let name = fields.next().filter(|name| !name.is_empty()).unwrap_or("Holiday");
+            let holiday = match fields.next().unwrap_or_default() {
+                "" => Holiday::closed(name),
+                staffing if staffing.eq_ignore_ascii_case("closed") => Holiday::closed(name),
+                staffing => Holiday::with_demand(name, parse_staffing(staffing).ok_or(error)?),
+            };
+            calendar.insert(date, holiday);
+        }
+        Ok(calendar)
+    }
+
+    /// Reads the all-day events of an iCalendar file as closures.
+    ///
+    /// Events spanning several days close every day up to their `DTEND`. An event with
+    /// an `X-STAFFING:Min-Max` property is open with that staffing instead.
+    pub fn parse_ical(input: &str) -> Result<Self, ParseCalendarError> {
+        // Lines starting with a space or tab continue the line before
+        let mut lines: Vec<(usize, String)> = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(rest), Some((_, last))) => last.push_str(rest),
+                _ => lines.push((index + 1, line.trim_end().to_string())),
+            }
+        }
+
+        let mut calendar = Self::new();
+        let mut event: Option<Event> = None;
+        for (line, content) in lines {
+            let error = ParseCalendarError { line };
+            let Some((property, value)) = content.split_once(':') else { continue };
+            let name = property.split(';').next().unwrap_or_default();
+
+            match (name.to_ascii_uppercase().as_str(), &mut event) {
+                ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                    event = Some(Event { start: None, last: None, holiday: Holiday::closed("Holiday") });
+                },
+                ("END", Some(current)) if value.eq_ignore_ascii_case("VEVENT") => {
+                    let start = current.start.ok_or(error)?;
+                    let last = current.last.map_or(start, |last| last.max(start));
+                    for date in start.range_to(last) {
+                        calendar.insert(date, current.holiday.clone());
+                    }
+                    event = None;
+                },
+                ("DTSTART", Some(current)) => current.start = Some(parse_ical_date(value).ok_or(error)?),
+                ("DTEND", Some(current)) => {
+                    // A date end is the day after the event, a date and time end is on its last day
+                    let date = parse_ical_date(value).ok_or(error)?;
+                    current.last = Some(if value.contains('T') { date } else { date.add_days(-1) });
+                },
+                ("SUMMARY", Some(current)) => {
+                    current.holiday.name = value.replace("\\,", ",").replace("\\;", ";").replace("\\n", " ");
+                },
+                ("X-STAFFING", Some(current)) => current.holiday.demand = Some(parse_staffing(value).ok_or(error)?),
+                _ => {},
+            }
+        }
+        Ok(calendar)
+    }
+}
+
+/// An iCalendar event being read.
+struct Event {
+    start: Option<Date>,
+    last: Option<Date>,
+    holiday: Holiday,
+}
+
+/// Parses staffing written as `Min-Max` or `Min`.
+fn parse_staffing(input: &str) -> Option<StaffingDemand> {
+    match input.split_once('-') {
+        Some((min, max)) => Some(StaffingDemand { min: min.trim().parse().ok()?, max: Some(max.trim().parse().ok()?) }),
+        None => Some(StaffingDemand { min: input.trim().parse().ok()?, max: None }),
+    }
+}
+
+/// Parses the date of an iCalendar `YYYYMMDD` or `YYYYMMDDTHHMMSS` value.
+fn parse_ical_date(value: &str) -> Option<Date> {
+    let value = value.trim();
+    let digits = value.get(..8).filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))?;
+    Date::new(digits[..4].parse().ok()?, digits[4..6].parse().ok()?, digits[6..].parse().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let calendar = Calendar::parse("\
+date,name,staffing
+2026-12-24,Christmas Eve,4-6
+# Closed all day
+2026-12-25,Christmas Day,closed
+2026-12-26,\"Boxing Day\",1
+
+2027-01-01,New Year's Day
+").unwrap();
+
+        let christmas_eve = Date::parse("2026-12-24").unwrap();
+        assert_eq!(calendar.get(christmas_eve), Some(&Holiday::with_demand("Christmas Eve", StaffingDemand { min: 4, max: Some(6) })));
+        assert!(!calendar.is_closed(christmas_eve));
+        assert!(calendar.is_closed(Date::parse("2026-12-25").unwrap()));
+        assert_eq!(calendar.get(Date::parse("2026-12-26").unwrap()).unwrap().to_string(), "Boxing Day (1 per shift)");
+        assert!(calendar.is_closed(Date::parse("2027-01-01").unwrap()));
+        assert_eq!(calendar.between(christmas_eve, Date::parse("2026-12-31").unwrap()).count(), 3);
+
+        assert_eq!(Calendar::parse("2026-12-25,Christmas\n2026-13-01,Nope"), Err(ParseCalendarError { line: 2 }));
+        assert_eq!(Calendar::parse("2026-12-24,Christmas Eve,lots"), Err(ParseCalendarError { line: 1 }));
+    }
+
+    #[test]
+    fn test_parse_ical() {
+        let calendar = Calendar::parse("\
+BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+DTSTART;VALUE=DATE:20261225
+DTEND;VALUE=DATE:20261227
+SUMMARY:Christmas\\, closed
+END:VEVENT
+BEGIN:VEVENT
+DTSTART:20261231T120000Z
+DTEND:20261231T180000Z
+SUMMARY:New Year's
+  Eve
+X-STAFFING:1-2
+END:VEVENT
+END:VCALENDAR
+").unwrap();
+
+        assert_eq!(calendar.iter().count(), 3);
+        assert_eq!(calendar.get(Date::parse("2026-12-25").unwrap()), Some(&Holiday::closed("Christmas, closed")));
+        assert!(calendar.is_closed(Date::parse("2026-12-26").unwrap()));
+        assert!(calendar.get(Date::parse("2026-12-27").unwrap()).is_none());
+        assert_eq!(calendar.get(Date::parse("2026-12-31").unwrap()),
+            Some(&Holiday::with_demand("New Year's Eve", StaffingDemand { min: 1, max: Some(2) })));
+
+        assert_eq!(Calendar::parse("BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:No date\nEND:VEVENT"), Err(ParseCalendarError { line: 4 }));
+        assert_eq!(Calendar::parse("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:202é1225\nEND:VEVENT"), Err(ParseCalendarError { line: 3 }));
+    }
+}
@@ -0,0 +1,595 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Calendar, Date, Days, Employee, Schedule, SchedulerError, Shifts, Weights};
+
+/// Number of employees needed on a shift.
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+pub struct StaffingDemand {
+    /// Minimum number of employees on the shift.
+    pub min: usize,
+
+    /// Maximum number of employees on the shift, `None` for no limit.
+    pub max: Option<usize>,
+}
+
+impl StaffingDemand {
+
+    /// Checks a number of employees is within the maximum.
+    pub fn allows(&self, count: usize) -> bool {
+        self.max.is_none_or(|max| count <= max)
+    }
+}
+
+/// Number of employees with a skill needed on a shift.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct SkillRequirement {
+    pub skill: String,
+    pub count: usize,
+}
+
+impl SkillRequirement {
+
+    pub fn new(skill: &str, count: usize) -> Self {
+        Self { skill: skill.into(), count }
+    }
+}
+
+/// An employee placed on a shift, or kept off it, before solving.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Assignment {
+    /// Name of the employee.
+    pub employee: String,
+    pub day: Days,
+    pub shift: Shifts,
+
+    /// Monday of the only week the assignment applies to when scheduling dates,
+    /// `None` for every week.
+    pub week: Option<Date>,
+}
+
+impl Assignment {
+
+    pub fn new(employee: &str, day: Days, shift: Shifts) -> Self {
+        Self { employee: employee.into(), day, shift, week: None }
+    }
+
+    /// An assignment on a single date.
+    pub fn on(employee: &str, date: Date, shift: Shifts) -> Self {
+        Self { employee: employee.into(), day: date.weekday(), shift, week: Some(date.week_start()) }
+    }
+
+    /// Checks the assignment is for the employee's shift in every week. Dated
+    /// assignments only apply once `Scheduler::get_roster` schedules their week.
+    pub fn matches(&self, employee: &Employee, day: Days, shift: &Shifts) -> bool {
+        self.week.is_none() && self.day == day && &self.shift == shift && self.employee == employee.name
+    }
+
+    /// Checks the assignment applies to the week starting on `week`.
+    pub fn in_week(&self, week: Date) -> bool {
+        self.week.is_none_or(|w| w == week)
+    }
+}
+
+impl fmt::Display for Assignment {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.week {
+            Some(week) => write!(f, "{} {} {}", self.employee, week.in_week(self.day), self.shift),
+            None => write!(f, "{} {} {}", self.employee, self.day, self.shift),
+        }
+    }
+}
+
+/// Forbids working a shift on the day after working another shift.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct RestRule {
+    /// Shift worked on the first day.
+    pub after: Shifts,
+
+    /// Shift that cannot be worked on the following day.
+    pub forbidden: Shifts,
+}
+
+impl RestRule {
+
+    /// Rest between closing the evening and opening the next morning.
+    pub fn no_morning_after_evening() -> Self {
+        Self { after: Shifts::evening(), forbidden: Shifts::morning() }
+    }
+}
+
+/// Shifts employees would rather not work, such as weekends, shared out evenly
+/// between them.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Undesirable {
+    /// Days the shifts are on, empty for every day.
+    pub days: Vec<Days>,
+
+    /// Shifts on those days, empty for every shift.
+    pub shifts: Vec<Shifts>,
+}
+
+impl Undesirable {
+
+    /// Every shift on Saturday and Sunday.
+    pub fn weekends() -> Self {
+        Self { days: vec![Days::Saturday, Days::Sunday], shifts: Vec::new() }
+    }
+
+    /// The evening shift on every day.
+    pub fn evenings() -> Self {
+        Self { days: Vec::new(), shifts: vec![Shifts::evening()] }
+    }
+
+    pub fn covers(&self, day: Days, shift: &Shifts) -> bool {
+        (self.days.is_empty() || self.days.contains(&day)) && (self.shifts.is_empty() || self.shifts.contains(shift))
+    }
+}
+
+impl fmt::Display for Undesirable {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let days: Vec<_> = self.days.iter().map(|d| d.to_string()).collect();
+        let shifts: Vec<_> = self.shifts.iter().map(|s| s.to_string()).collect();
+        match (days.is_empty(), shifts.is_empty()) {
+            (true, true) => write!(f, "Every shift"),
+            (false, true) => write!(f, "{}", days.join("/")),
+            (true, false) => write!(f, "{}", shifts.join("/")),
+            (false, false) => write!(f, "{} {}", days.join("/"), shifts.join("/")),
+        }
+    }
+}
+
+/// How the `Scheduler` orders employees before solving, deciding who the solvers
+/// favour when employees are otherwise equal.
+#[derive(Debug, Copy, Clone, Default, Eq, Hash, PartialEq)]
+pub enum TieBreak {
+    /// The order employees were added in.
+    #[default]
+    RosterOrder,
+
+    /// Alphabetical order of names.
+    Name,
+
+    /// Most senior first, then roster order.
+    Seniority,
+
+    /// Shuffled by the config's `seed`, the same seed always giving the same order.
+    Random,
+}
+
+impl TieBreak {
+
+    pub fn iter() -> impl Iterator<Item = TieBreak> {
+        [Self::RosterOrder, Self::Name, Self::Seniority, Self::Random].iter().copied()
+    }
+
+    /// Parses a policy by its displayed name, ignoring case.
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::iter().find(|tie_break| tie_break.to_string().eq_ignore_ascii_case(input.trim()))
+    }
+
+    /// The employees in the order the solvers should favour them.
+    pub fn order(&self, employees: &[Employee], seed: u64) -> Vec<Employee> {
+        let mut ordered = employees.to_vec();
+        match self {
+            Self::RosterOrder => {},
+            Self::Name => ordered.sort_by(|a, b| a.name.cmp(&b.name)),
+            Self::Seniority => ordered.sort_by_key(|e| std::cmp::Reverse(e.seniority)),
+            Self::Random => {
+                // Fisher-Yates shuffle
+                let mut rng = SplitMix64(seed);
+                for i in (1..ordered.len()).rev() {
+                    ordered.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+                }
+            },
+        }
+        ordered
+    }
+}
+
+impl fmt::Display for TieBreak {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RosterOrder => write!(f, "roster"),
+            Self::Name => write!(f, "name"),
+            Self::Seniority => write!(f, "seniority"),
+            Self::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// Small seeded random number generator, kept in the crate so a seed gives the same
+/// schedule on every platform and version.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Assignments the backtracking solver tries by default before giving up.
+const DEFAULT_SEARCH_LIMIT: usize = 200_000;
+
+/// Rules used by the `Scheduler` when building a schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleConfig {
+    /// Days of the week that need to be staffed.
+    pub operational_days: Vec<Days>,
+
+    /// Shifts worked on every operational day.
+    pub shifts: Vec<Shifts>,
+
+    /// Minimum number of employees on every shift.
+    pub shift_min_emps: usize,
+
+    /// Maximum number of employees on every shift, `None` for no limit.
+    pub shift_max_emps: Option<usize>,
+
+    /// Staffing for specific shifts, overriding `shift_min_emps` and `shift_max_emps`.
+    pub demand: HashMap<(Days, Shifts), StaffingDemand>,
+
+    /// Skilled employees needed on every shift.
+    pub shift_skills: Vec<SkillRequirement>,
+
+    /// Skills for specific shifts, overriding `shift_skills`.
+    pub skill_demand: HashMap<(Days, Shifts), Vec<SkillRequirement>>,
+
+    /// Maximum number of days an employee can work in a week.
+    pub emp_max_days: usize,
+
+    /// Minimum rest required between shifts on consecutive days.
+    pub rest_rules: Vec<RestRule>,
+
+    /// Minimum hours between the end of a shift and the start of the next day's shift.
+    pub min_rest_hours: Option<usize>,
+
+    /// Maximum number of days in a row an employee can work.
+    pub max_consecutive_days: Option<usize>,
+
+    /// Number of days in a row every employee must have off at least once a week.
+    pub min_days_off_in_a_row: Option<usize>,
+
+    /// Treat the week as repeating, so Sunday is followed by the next Monday.
+    pub wrap_weeks: bool,
+
+    /// Published schedule of the week before, so the rest, consecutive days and
+    /// least scheduled rules carry on into Monday. Takes the place of `wrap_weeks`
+    /// going into Monday.
+    pub history: Option<Schedule>,
+
+    /// Holidays closing dates or changing their staffing, used when scheduling dates
+    /// with `Scheduler::get_roster`.
+    pub calendar: Calendar,
+
+    /// Employees who must work a shift, with the rest of the schedule filled around them.
+    pub pinned: Vec<Assignment>,
+
+    /// Employees who must not work a shift.
+    pub forbidden: Vec<Assignment>,
+
+    /// How much each goal counts when scoring schedules.
+    pub weights: Weights,
+
+    /// Groups of shifts shared out evenly between employees, none by default.
+    pub undesirable: Vec<Undesirable>,
+
+    /// Earlier published weeks, oldest first, counted when sharing out `undesirable` shifts.
+    pub past_weeks: Vec<Schedule>,
+
+    /// Number of the latest `past_weeks` counted, `0` to share out each week on its own.
+    /// `Scheduler::get_roster` adds each week it schedules to the past weeks of the next.
+    pub fairness_weeks: usize,
+
+    /// Order the solvers favour employees in when they are otherwise equal.
+    pub tie_break: TieBreak,
+
+    /// Seed for the `Random` tie break.
+    pub seed: u64,
+
+    /// Assignments the backtracking solver tries before giving up, `None` for no limit.
+    pub search_limit: Option<usize>,
+}
+
+impl ScheduleConfig {
+
+    /// Staffing needed on a shift.
+    pub fn demand(&self, day: Days, shift: &Shifts) -> StaffingDemand {
+        self.demand.get(&(day, shift.clone())).copied()
+            .unwrap_or(StaffingDemand { min: self.shift_min_emps, max: self.shift_max_emps })
+    }
+
+    /// Checks no operational shift needs more employees than its maximum allows.
+    pub fn check_demand(&self) -> Result<(), SchedulerError> {
+        for &day in &self.operational_days {
+            for shift in &self.shifts {
+                let demand = self.demand(day, shift);
+                if let Some(max) = demand.max && demand.min > max {
+                    return Err(SchedulerError::InvalidDemand { day, shift: shift.clone(), min: demand.min, max });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Skilled employees needed on a shift.
+    pub fn required_skills(&self, day: Days, shift: &Shifts) -> &[SkillRequirement] {
+        self.skill_demand.get(&(day, shift.clone())).unwrap_or(&self.shift_skills)
+    }
+
+    /// Pins applying to every week, leaving out dated pins.
+    pub fn weekly_pins(&self) -> impl Iterator<Item = &Assignment> {
+        self.pinned.iter().filter(|pin| pin.week.is_none())
+    }
+
+    /// Checks an employee is pinned to a shift.
+    pub fn is_pinned(&self, employee: &Employee, day: Days, shift: &Shifts) -> bool {
+        self.pinned.iter().any(|pin| pin.matches(employee, day, shift))
+    }
+
+    /// Checks an employee is available for a shift and not forbidden from working it.
+    pub fn can_assign(&self, employee: &Employee, day: Days, shift: &Shifts) -> bool {
+        employee.is_available(day, shift) && !self.forbidden.iter().any(|f| f.matches(employee, day, shift))
+    }
+
+    /// Employees pinned to a shift, in roster order.
+    pub fn pinned_employees<'a>(&self, employees: &'a [Employee], day: Days, shift: &Shifts) -> Vec<&'a Employee> {
+        employees.iter().filter(|e| self.is_pinned(e, day, shift)).collect()
+    }
+
+    /// Number of employee shifts needed to staff the whole week.
+    pub fn required_shifts(&self) -> usize {
+        self.operational_days.iter()
+            .flat_map(|&day| self.shifts.iter().map(move |shift| self.demand(day, shift).min))
+            .sum()
+    }
+
+    /// Number of shifts a single employee can work in a week.
+    pub fn employee_capacity(&self) -> usize {
+        self.emp_max_days.min(self.operational_days.len())
+    }
+
+    /// The day after `day`, wrapping from Sunday to Monday when `wrap_weeks` is set.
+    pub fn next_day(&self, day: Days) -> Option<Days> {
+        day.next_day().or(if self.wrap_weeks { Some(Days::Monday) } else { None })
+    }
+
+    /// The day before `day`, wrapping from Monday to Sunday when `wrap_weeks` is set
+    /// and there is no `history`.
+    pub fn previous_day(&self, day: Days) -> Option<Days> {
+        day.previous_day().or(if self.wrap_weeks && self.history.is_none() { Some(Days::Sunday) } else { None })
+    }
+
+    /// Shifts an employee worked in the `history`, matching employees by name.
+    fn history_for(&self, employee: &Employee) -> Vec<(Days, &Shifts)> {
+        self.history.iter()
+            .flat_map(|history| history.iter())
+            .filter(|(_, _, employees)| employees.iter().any(|e| e.name == employee.name))
+            .map(|(day, shift, _)| (day, shift))
+            .collect()
+    }
+
+    /// Shift an employee worked on the Sunday before the week, from the `history`.
+    pub fn carried_shift(&self, employee: &Employee) -> Option<&Shifts> {
+        self.history_for(employee).into_iter()
+            .find(|&(day, _)| day == Days::Sunday)
+            .map(|(_, shift)| shift)
+    }
+
+    /// Number of days an employee worked in the `history`.
+    pub fn history_days(&self, employee: &Employee) -> usize {
+        let mut days: Vec<_> = self.history_for(employee).into_iter().map(|(day, _)| day).collect();
+        days.dedup();
+        days.len()
+    }
+
+    /// Number of shifts in an `undesirable` group an employee works in `schedule` and
+    /// the counted `past_weeks`, matching past weeks by name.
+    pub fn undesirable_worked(&self, employee: &Employee, group: &Undesirable, schedule: &Schedule) -> usize {
+        let counted = &self.past_weeks[self.past_weeks.len().saturating_sub(self.fairness_weeks)..];
+        let past = counted.iter()
+            .flat_map(|week| week.iter())
+            .filter(|(day, shift, employees)| group.covers(*day, shift) && employees.iter().any(|e| e.name == employee.name))
+            .count();
+        past + schedule.shifts_for(employee).iter().filter(|(day, shift)| group.covers(*day, shift)).count()
+    }
+
+    /// Checks working on `days` respects the consecutive working days and days off rules.
+    pub fn allows_work_days(&self, days: &[Days]) -> bool {
+        let worked: Vec<bool> = Days::iter().map(|day| days.contains(&day)).collect();
+
+        let max_worked = self.max_consecutive_days.unwrap_or(usize::MAX);
+        let min_off = self.min_days_off_in_a_row.unwrap_or(0);
+
+        longest_run(&worked, true, self.wrap_weeks) <= max_worked
+            && longest_run(&worked, false, self.wrap_weeks) >= min_off
+    }
+
+    /// Checks an employee working on `days` respects the consecutive working days and
+    /// days off rules, continuing runs from the end of their `history` into Monday.
+    pub fn allows_work_days_for(&self, employee: &Employee, days: &[Days]) -> bool {
+        if self.history.is_none() {
+            return self.allows_work_days(days);
+        }
+
+        let history: Vec<_> = self.history_for(employee).into_iter().map(|(day, _)| day).collect();
+        let before: Vec<bool> = Days::iter().map(|day| history.contains(&day)).collect();
+        let worked: Vec<bool> = Days::iter().map(|day| days.contains(&day)).collect();
+
+        // A run reaching Monday adds the matching run ending the Sunday before
+        let run_into_week = |value: bool| {
+            let leading = worked.iter().take_while(|&&d| d == value).count();
+            let trailing = before.iter().rev().take_while(|&&d| d == value).count();
+            let carried = if leading > 0 { leading + trailing } else { 0 };
+            longest_run(&worked, value, false).max(carried)
+        };
+
+        let max_worked = self.max_consecutive_days.unwrap_or(usize::MAX);
+        let min_off = self.min_days_off_in_a_row.unwrap_or(0);
+
+        run_into_week(true) <= max_worked && run_into_week(false) >= min_off
+    }
+
+    /// Checks a shift can be worked the day after working `previous`.
+    pub fn rest_allows(&self, previous: &Shifts, next: &Shifts) -> bool {
+        let min_rest = self.min_rest_hours.map_or(0, |hours| hours as u32 * 60);
+        previous.rest_before(next) >= min_rest
+            && !self.rest_rules.iter().any(|rule| &rule.after == previous && &rule.forbidden == next)
+    }
+}
+
+impl Default for ScheduleConfig {
+
+    fn default() -> Self {
+        Self {
+            operational_days: Days::iter().collect(),
+            shifts: Shifts::standard(),
+            shift_min_emps: 2,
+            shift_max_emps: None,
+            demand: HashMap::new(),
+            shift_skills: Vec::new(),
+            skill_demand: HashMap::new(),
+            emp_max_days: 5,
+            rest_rules: Vec::new(),
+            min_rest_hours: None,
+            max_consecutive_days: None,
+            min_days_off_in_a_row: None,
+            wrap_weeks: false,
+            history: None,
+            calendar: Calendar::new(),
+            pinned: Vec::new(),
+            forbidden: Vec::new(),
+            weights: Weights::default(),
+            undesirable: Vec::new(),
+            past_weeks: Vec::new(),
+            fairness_weeks: 0,
+            tie_break: TieBreak::RosterOrder,
+            seed: 0,
+            search_limit: Some(DEFAULT_SEARCH_LIMIT),
+        }
+    }
+}
+
+/// Length of the longest run of `value` in `days`, optionally wrapping around the end.
+fn longest_run(days: &[bool], value: bool, wrap: bool) -> usize {
+    if days.iter().all(|&d| d == value) {
+        return days.len();
+    }
+
+    let len = if wrap { days.len() * 2 } else { days.len() };
+    let mut longest = 0;
+    let mut current = 0;
+    for i in 0..len {
+        if days[i % days.len()] == value {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest.min(days.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::employee;
+
+    #[test]
+    fn test_allows_work_days() {
+        use Days::*;
+
+        let config = ScheduleConfig { max_consecutive_days: Some(3), ..Default::default() };
+        assert!(config.allows_work_days(&[Monday, Tuesday, Wednesday, Friday, Saturday, Sunday]));
+        assert!(!config.allows_work_days(&[Tuesday, Wednesday, Thursday, Friday]));
+
+        // Sunday into Monday only counts when the week repeats
+        let config = ScheduleConfig { wrap_weeks: true, ..config };
+        assert!(!config.allows_work_days(&[Monday, Tuesday, Wednesday, Friday, Saturday, Sunday]));
+        assert!(config.allows_work_days(&[Monday, Wednesday, Thursday, Saturday, Sunday]));
+
+        let config = ScheduleConfig { min_days_off_in_a_row: Some(2), ..Default::default() };
+        assert!(config.allows_work_days(&[Monday, Tuesday, Wednesday, Thursday, Friday]));
+        assert!(!config.allows_work_days(&[Monday, Wednesday, Friday, Sunday]));
+        assert!(!config.allows_work_days(&[Tuesday, Wednesday, Thursday, Friday, Saturday]));
+
+        let config = ScheduleConfig { wrap_weeks: true, ..config };
+        assert!(config.allows_work_days(&[Tuesday, Wednesday, Thursday, Friday, Saturday]));
+    }
+
+    #[test]
+    fn test_min_rest_hours() {
+        let night = Shifts::parse("Night=22:00-06:00").unwrap();
+
+        let config = ScheduleConfig { min_rest_hours: Some(11), ..Default::default() };
+        assert!(!config.rest_allows(&Shifts::evening(), &Shifts::morning()));
+        assert!(config.rest_allows(&Shifts::afternoon(), &Shifts::morning()));
+        assert!(config.rest_allows(&Shifts::evening(), &Shifts::afternoon()));
+        assert!(!config.rest_allows(&night, &Shifts::evening()));
+
+        let config = ScheduleConfig { min_rest_hours: Some(8), ..config };
+        assert!(config.rest_allows(&Shifts::evening(), &Shifts::morning()));
+        assert!(config.rest_allows(&night, &Shifts::evening()));
+        assert!(!config.rest_allows(&night, &Shifts::afternoon()));
+    }
+
+    #[test]
+    fn test_history() {
+        use Days::*;
+
+        let alice = employee("Alice");
+        let mut history = Schedule::new();
+        for day in [Thursday, Friday, Saturday, Sunday] {
+            history.insert(day, Shifts::evening(), vec![alice.clone()]);
+        }
+
+        let config = ScheduleConfig { max_consecutive_days: Some(5), ..Default::default() };
+        assert!(config.allows_work_days_for(&alice, &[Monday, Tuesday, Wednesday]));
+
+        let config = ScheduleConfig { history: Some(history), ..config };
+        assert_eq!(config.carried_shift(&alice), Some(&Shifts::evening()));
+        assert_eq!(config.history_days(&alice), 4);
+        assert!(config.allows_work_days_for(&alice, &[Monday, Wednesday, Thursday]));
+        assert!(!config.allows_work_days_for(&alice, &[Monday, Tuesday, Thursday]));
+
+        // Days off at the end of the history count towards a run of days off
+        let config = ScheduleConfig { max_consecutive_days: None, min_days_off_in_a_row: Some(2), ..config };
+        assert!(config.allows_work_days_for(&alice, &[Monday, Tuesday, Wednesday, Thursday, Friday]));
+        let bob = employee("Bob");
+        assert!(config.allows_work_days_for(&bob, &[Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday]));
+        assert!(!config.allows_work_days(&[Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday]));
+    }
+
+    #[test]
+    fn test_tie_break() {
+        let employees: Vec<_> = [("Carol", 1), ("Alice", 3), ("Bob", 1), ("Dave", 3)].into_iter()
+            .map(|(name, seniority)| Employee { seniority, ..employee(name) })
+            .collect();
+        let names = |tie_break: TieBreak, seed| -> Vec<String> {
+            tie_break.order(&employees, seed).into_iter().map(|e| e.name).collect()
+        };
+
+        assert_eq!(names(TieBreak::RosterOrder, 0), ["Carol", "Alice", "Bob", "Dave"]);
+        assert_eq!(names(TieBreak::Name, 0), ["Alice", "Bob", "Carol", "Dave"]);
+        assert_eq!(names(TieBreak::Seniority, 0), ["Alice", "Dave", "Carol", "Bob"]);
+
+        // The same seed always gives the same order, and the order differs between seeds
+        let mut shuffled = names(TieBreak::Random, 7);
+        assert_eq!(shuffled, names(TieBreak::Random, 7));
+        assert!((0..10).any(|seed| names(TieBreak::Random, seed) != shuffled));
+        shuffled.sort();
+        assert_eq!(shuffled, names(TieBreak::Name, 0));
+
+        assert_eq!(TieBreak::parse(" Seniority"), Some(TieBreak::Seniority));
+        assert_eq!(TieBreak::parse("roster"), Some(TieBreak::RosterOrder));
+        assert_eq!(TieBreak::parse("age"), None);
+    }
+}
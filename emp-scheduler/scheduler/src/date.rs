@@ -0,0 +1,128 @@
+use std::fmt;
+
+use crate::Days;
+
+/// A calendar date, stored as days since 1970-01-01.
+#[derive(Debug, Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    days: i32,
+}
+
+impl Date {
+
+    /// Creates a date, `None` if it does not exist in the calendar or its year is
+    /// outside `1..=9999`.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=9999).contains(&year) || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        // Days from civil, counting years from March so the leap day comes last
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month_from_march = (month + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year as i32;
+        Some(Self { days: era * 146097 + day_of_era - 719468 })
+    }
+
+    /// Parses a date written as `YYYY-MM-DD`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut parts = input.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Self::new(year, month, day)
+    }
+
+    /// Year, month and day of the date.
+    pub fn ymd(&self) -> (i32, u32, u32) {
+        let days = self.days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year, month, day)
+    }
+
+    pub fn weekday(&self) -> Days {
+        // 1970-01-01 was a Thursday
+        Days::iter().nth((self.days + 3).rem_euclid(7) as usize).expect("Seven days in a week")
+    }
+
+    pub fn add_days(&self, days: i32) -> Self {
+        Self { days: self.days + days }
+    }
+
+    /// The Monday starting the date's week.
+    pub fn week_start(&self) -> Self {
+        self.add_days(-((self.days + 3).rem_euclid(7)))
+    }
+
+    /// The date of a weekday in the same Monday to Sunday week.
+    pub fn in_week(&self, day: Days) -> Self {
+        let offset = Days::iter().position(|d| d == day).expect("Day is in the week");
+        self.week_start().add_days(offset as i32)
+    }
+
+    /// Days from this date until `other`, negative if `other` is earlier.
+    pub fn days_until(&self, other: Date) -> i32 {
+        other.days - self.days
+    }
+
+    /// Every date from this date up to and including `end`.
+    pub fn range_to(self, end: Date) -> impl Iterator<Item = Date> {
+        (self.days..=end.days).map(|days| Date { days })
+    }
+}
+
+impl fmt::Display for Date {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dates() {
+        let date = Date::parse("2026-11-02").unwrap();
+        assert_eq!(date.ymd(), (2026, 11, 2));
+        assert_eq!(date.to_string(), "2026-11-02");
+        assert_eq!(date.weekday(), Days::Monday);
+        assert_eq!(Date::new(1970, 1, 1).unwrap().weekday(), Days::Thursday);
+
+        assert_eq!(Date::parse("2024-02-29").unwrap().add_days(1), Date::new(2024, 3, 1).unwrap());
+        assert_eq!(Date::parse("2025-02-29"), None);
+        assert_eq!(Date::parse("2026-13-01"), None);
+        assert_eq!(Date::parse("2000000000-01-01"), None);
+        assert_eq!(Date::parse("0000-01-01"), None);
+        assert_eq!(Date::parse("9999-12-31").unwrap().to_string(), "9999-12-31");
+        assert_eq!(Date::new(2026, 12, 31).unwrap().add_days(1).to_string(), "2027-01-01");
+
+        let sunday = Date::parse("2026-11-08").unwrap();
+        assert_eq!(sunday.weekday(), Days::Sunday);
+        assert_eq!(sunday.week_start(), date);
+        assert_eq!(date.in_week(Days::Sunday), sunday);
+        assert_eq!(date.days_until(sunday), 6);
+        assert_eq!(date.range_to(sunday).count(), 7);
+    }
+}
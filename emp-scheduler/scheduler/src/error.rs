@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Date, Days, Shifts};
+
+/// Reasons a schedule could not be created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulerError {
+    /// The employees cannot cover the total number of shifts in the week.
+    InsufficientCapacity {
+        required: usize,
+        available: usize,
+    },
+
+    /// Not enough employees were left to staff a shift.
+    ShiftUnderstaffed {
+        day: Days,
+        shift: Shifts,
+        needed: usize,
+        found: usize,
+    },
+
+    /// A shift cannot be staffed because employees are unavailable or on time off.
+    TimeOffConflict {
+        day: Days,
+        shift: Shifts,
+        needed: usize,
+        found: usize,
+        unavailable: Vec<String>,
+    },
+
+    /// Not enough employees with a skill could be found for a shift.
+    MissingSkill {
+        day: Days,
+        shift: Shifts,
+        skill: String,
+        needed: usize,
+        found: usize,
+    },
+
+    /// An employee could not be given the hours guaranteed by their contract.
+    ContractHoursUnmet {
+        employee: String,
+        min_hours: usize,
+        scheduled_minutes: u32,
+    },
+
+    /// A shift needs more employees than its maximum allows.
+    InvalidDemand {
+        day: Days,
+        shift: Shifts,
+        min: usize,
+        max: usize,
+    },
+
+    /// An employee is pinned to a shift they cannot work.
+    PinConflict {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// No assignment of employees satisfies the scheduling rules.
+    Infeasible,
+
+    /// The solver gave up searching before finding a schedule or proving none exists.
+    SearchLimitReached {
+        tried: usize,
+    },
+
+    /// A week of a roster could not be scheduled.
+    InWeek {
+        week_of: Date,
+        error: Box<SchedulerError>,
+    },
+}
+
+impl fmt::Display for SchedulerError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InsufficientCapacity { required, available } => {
+                write!(f, "Not enough employees: {} shifts required, {} available", required, available)
+            },
+            Self::ShiftUnderstaffed { day, shift, needed, found } => {
+                write!(f, "Not enough employees for {} {}: {} needed, {} found", day, shift, needed, found)
+            },
+            Self::TimeOffConflict { day, shift, needed, found, unavailable } => {
+                write!(f, "Not enough employees for {} {} because of time off: {} needed, {} found ({} unavailable or on time off)",
+                    day, shift, needed, found, unavailable.join(", "))
+            },
+            Self::MissingSkill { day, shift, skill, needed, found } => {
+                write!(f, "Not enough employees with skill {} for {} {}: {} needed, {} found", skill, day, shift, needed, found)
+            },
+            Self::ContractHoursUnmet { employee, min_hours, scheduled_minutes } => {
+                write!(f, "Could not schedule {} for their contracted {} hours: {:.1} hours scheduled",
+                    employee, min_hours, *scheduled_minutes as f32 / 60.0)
+            },
+            Self::InvalidDemand { day, shift, min, max } => {
+                write!(f, "{} {} needs at least {} employees but allows at most {}", day, shift, min, max)
+            },
+            Self::PinConflict { employee, day, shift } => {
+                write!(f, "{} is pinned to {} {} but cannot work it", employee, day, shift)
+            },
+            Self::Infeasible => {
+                write!(f, "No schedule satisfies the scheduling rules")
+            },
+            Self::SearchLimitReached { tried } => {
+                write!(f, "Gave up after trying {} assignments without finding a schedule", tried)
+            },
+            Self::InWeek { week_of, error } => {
+                write!(f, "Week of {}: {}", week_of, error)
+            },
+        }
+    }
+}
+
+impl Error for SchedulerError {}
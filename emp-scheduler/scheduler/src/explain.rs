@@ -0,0 +1,369 @@
+use std::fmt;
+
+use crate::{Date, Days, Employee, ScheduleConfig, Scheduler, SchedulerError, Shifts};
+
+/// Why an employee cannot work a shift.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    /// The employee can never work the shift.
+    UnavailableShift,
+
+    /// The employee can never work on the day.
+    UnavailableDay,
+
+    /// The employee has approved time off.
+    TimeOff,
+
+    /// The employee is kept off the shift.
+    Forbidden,
+
+    /// The employee is pinned to another shift on the day.
+    PinnedElsewhere(Shifts),
+
+    /// The employee does not have a skill the shift is short of.
+    MissingSkill(String),
+}
+
+impl fmt::Display for Exclusion {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnavailableShift => write!(f, "unavailable for the shift"),
+            Self::UnavailableDay => write!(f, "unavailable on the day"),
+            Self::TimeOff => write!(f, "on time off"),
+            Self::Forbidden => write!(f, "kept off the shift"),
+            Self::PinnedElsewhere(shift) => write!(f, "pinned to {}", shift),
+            Self::MissingSkill(skill) => write!(f, "does not have skill {}", skill),
+        }
+    }
+}
+
+/// A change to the employees or rules that would help a schedule be found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suggestion {
+    /// Hire or add employees, able to work `shift` and with `skill` when given.
+    AddEmployees {
+        count: usize,
+        shift: Option<Shifts>,
+        skill: Option<String>,
+    },
+
+    /// Need fewer employees on a shift.
+    LowerStaffing {
+        day: Days,
+        shift: Shifts,
+        to: usize,
+    },
+
+    /// Let employees work more days in the week.
+    RaiseMaxDays {
+        to: usize,
+    },
+
+    /// Unpin an employee from a shift.
+    RemovePin {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// Guarantee an employee fewer hours.
+    LowerContract {
+        employee: String,
+        to_hours: usize,
+    },
+}
+
+impl fmt::Display for Suggestion {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AddEmployees { count, shift, skill } => {
+                write!(f, "Add {} ", count)?;
+                if let Some(shift) = shift {
+                    write!(f, "{}-capable ", shift)?;
+                }
+                write!(f, "{}", if *count == 1 { "employee" } else { "employees" })?;
+                if let Some(skill) = skill {
+                    write!(f, " with skill {}", skill)?;
+                }
+                Ok(())
+            },
+            Self::LowerStaffing { day, shift, to } => write!(f, "Lower the staffing of {} {} to {}", day, shift, to),
+            Self::RaiseMaxDays { to } => write!(f, "Allow employees to work up to {} days", to),
+            Self::RemovePin { employee, day, shift } => write!(f, "Unpin {} from {} {}", employee, day, shift),
+            Self::LowerContract { employee, to_hours } => write!(f, "Guarantee {} at most {} hours", employee, to_hours),
+        }
+    }
+}
+
+/// Why a schedule could not be created, and what would help.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub error: SchedulerError,
+
+    /// Week of a roster the error is in.
+    pub week_of: Option<Date>,
+
+    /// The shift that ran out of employees, or the shift with the fewest to spare,
+    /// `None` when no one shift is to blame.
+    pub slot: Option<(Days, Shifts)>,
+
+    /// Employees the shift needs, or with the missing skill.
+    pub needed: usize,
+
+    /// Employees who could work the shift.
+    pub eligible: usize,
+
+    /// Employees who could not work the shift, and why.
+    pub excluded: Vec<(String, Exclusion)>,
+
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl fmt::Display for Explanation {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.week_of {
+            Some(week_of) => writeln!(f, "Week of {}: {}", week_of, self.error)?,
+            None => writeln!(f, "{}", self.error)?,
+        }
+        if let Some((day, shift)) = &self.slot {
+            writeln!(f, "{} {}: {} needed, {} eligible", day, shift, self.needed, self.eligible)?;
+        }
+        for (employee, exclusion) in &self.excluded {
+            writeln!(f, "  {} is {}", employee, exclusion)?;
+        }
+        if !self.suggestions.is_empty() {
+            writeln!(f, "Suggestions:")?;
+            for suggestion in &self.suggestions {
+                writeln!(f, "  {}", suggestion)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Scheduler {
+
+    /// Explains an error from `get_schedule`. Errors from `get_roster` need the roster's
+    /// dates, given to `explain_roster`.
+    pub fn explain(&self, error: &SchedulerError) -> Explanation {
+        self.explain_week(error, &self.config, None)
+    }
+
+    /// Explains an error from `get_roster` for the dates from `start` to `end`.
+    pub fn explain_roster(&self, error: &SchedulerError, start: Date, end: Date) -> Explanation {
+        match error {
+            SchedulerError::InWeek { week_of, error } => {
+                let config = self.failed_week_config(*week_of, start, end);
+                self.explain_week(error, &config, Some(*week_of))
+            },
+            _ => self.explain_week(error, &self.config, None),
+        }
+    }
+
+    /// Rules `get_roster` scheduled the failing week with, scheduling the weeks before it
+    /// again for its history and past weeks.
+    fn failed_week_config(&self, week_of: Date, start: Date, end: Date) -> ScheduleConfig {
+        let mut history = self.config.history.clone();
+        let mut past_weeks = self.config.past_weeks.clone();
+
+        let mut week = start.week_start();
+        while week < week_of {
+            let config = ScheduleConfig { past_weeks: past_weeks.clone(), ..self.week_config(week, start, end, history.clone()) };
+            let Ok(schedule) = self.schedule_week(&config) else {
+                break;
+            };
+            self.count_past_week(&mut past_weeks, &schedule);
+            history = Some(schedule);
+            week = week.add_days(7);
+        }
+
+        ScheduleConfig { past_weeks, ..self.week_config(week_of, start, end, history) }
+    }
+
+    fn explain_week(&self, error: &SchedulerError, config: &ScheduleConfig, week_of: Option<Date>) -> Explanation {
+        let mut explanation = Explanation {
+            error: error.clone(),
+            week_of,
+            slot: None,
+            needed: 0,
+            eligible: 0,
+            excluded: Vec::new(),
+            suggestions: Vec::new(),
+        };
+
+        let (day, shift, skill) = match error {
+            SchedulerError::ShiftUnderstaffed { day, shift, .. } | SchedulerError::TimeOffConflict { day, shift, .. } => {
+                (*day, shift.clone(), None)
+            },
+            SchedulerError::MissingSkill { day, shift, skill, .. } => (*day, shift.clone(), Some(skill.clone())),
+            SchedulerError::PinConflict { employee, day, shift } => {
+                if let Some(e) = self.employees.iter().find(|e| &e.name == employee)
+                    && let Some(exclusion) = exclusion(e, config, *day, shift, None) {
+                    explanation.excluded.push((employee.clone(), exclusion));
+                }
+                explanation.suggestions.push(Suggestion::RemovePin { employee: employee.clone(), day: *day, shift: shift.clone() });
+                return explanation;
+            },
+            SchedulerError::ContractHoursUnmet { employee, scheduled_minutes, .. } => {
+                let to_hours = *scheduled_minutes as usize / 60;
+                explanation.suggestions.push(Suggestion::LowerContract { employee: employee.clone(), to_hours });
+                return explanation;
+            },
+            SchedulerError::InvalidDemand { day, shift, max, .. } => {
+                explanation.suggestions.push(Suggestion::LowerStaffing { day: *day, shift: shift.clone(), to: *max });
+                return explanation;
+            },
+            SchedulerError::InsufficientCapacity { required, available } => {
+                let capacity = config.employee_capacity().max(1);
+                let count = required.saturating_sub(*available).div_ceil(capacity);
+                explanation.suggestions.push(Suggestion::AddEmployees { count, shift: None, skill: None });
+
+                let to = required.div_ceil(self.employees.len().max(1));
+                if to <= config.operational_days.len() {
+                    explanation.suggestions.push(Suggestion::RaiseMaxDays { to });
+                }
+                return explanation;
+            },
+            SchedulerError::Infeasible | SchedulerError::SearchLimitReached { .. } | SchedulerError::InWeek { .. } => match self.tightest_slot(config) {
+                Some((day, shift)) => (day, shift, None),
+                None => return explanation,
+            },
+        };
+
+        let (needed, excluded) = match &skill {
+            Some(skill) => {
+                let needed = config.required_skills(day, &shift).iter()
+                    .find(|required| &required.skill == skill)
+                    .map_or(0, |required| required.count);
+                (needed, self.excluded(config, day, &shift, Some(skill)))
+            },
+            None => (config.demand(day, &shift).min, self.excluded(config, day, &shift, None)),
+        };
+        let eligible = self.employees.len() - excluded.len();
+
+        if eligible < needed {
+            explanation.suggestions.push(Suggestion::AddEmployees { count: needed - eligible, shift: Some(shift.clone()), skill: skill.clone() });
+            if skill.is_none() {
+                explanation.suggestions.push(Suggestion::LowerStaffing { day, shift: shift.clone(), to: eligible });
+            }
+        } else {
+            // Enough employees could work the shift, but they are needed on other days
+            if config.emp_max_days < config.operational_days.len() {
+                explanation.suggestions.push(Suggestion::RaiseMaxDays { to: config.emp_max_days + 1 });
+            }
+            explanation.suggestions.push(Suggestion::AddEmployees { count: 1, shift: Some(shift.clone()), skill: None });
+        }
+
+        explanation.slot = Some((day, shift));
+        explanation.needed = needed;
+        explanation.eligible = eligible;
+        explanation.excluded = excluded;
+        explanation
+    }
+
+    /// Employees who cannot work a shift, with the first reason found for each.
+    fn excluded(&self, config: &ScheduleConfig, day: Days, shift: &Shifts, skill: Option<&str>) -> Vec<(String, Exclusion)> {
+        self.employees.iter()
+            .filter_map(|e| exclusion(e, config, day, shift, skill).map(|exclusion| (e.name.clone(), exclusion)))
+            .collect()
+    }
+
+    /// The shift with the fewest eligible employees to spare.
+    fn tightest_slot(&self, config: &ScheduleConfig) -> Option<(Days, Shifts)> {
+        config.operational_days.iter()
+            .flat_map(|&day| config.shifts.iter().map(move |shift| (day, shift.clone())))
+            .min_by_key(|(day, shift)| {
+                let eligible = self.employees.len() - self.excluded(config, *day, shift, None).len();
+                eligible as isize - config.demand(*day, shift).min as isize
+            })
+    }
+}
+
+/// Why an employee cannot work a shift, `None` when they can.
+fn exclusion(employee: &Employee, config: &ScheduleConfig, day: Days, shift: &Shifts, skill: Option<&str>) -> Option<Exclusion> {
+    if !employee.can_work(shift) {
+        Some(Exclusion::UnavailableShift)
+    } else if employee.unavailable_days.contains(&day) {
+        Some(Exclusion::UnavailableDay)
+    } else if employee.on_time_off(day, shift) {
+        Some(Exclusion::TimeOff)
+    } else if !config.can_assign(employee, day, shift) {
+        Some(Exclusion::Forbidden)
+    } else if let Some(pin) = config.weekly_pins().find(|pin| pin.employee == employee.name && pin.day == day && &pin.shift != shift) {
+        Some(Exclusion::PinnedElsewhere(pin.shift.clone()))
+    } else {
+        skill.filter(|skill| !employee.has_skill(skill)).map(|skill| Exclusion::MissingSkill(skill.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Assignment, BacktrackingSolver, TimeOff};
+    use crate::tests::employee;
+
+    #[test]
+    fn test_explain() {
+        let evening = Shifts::evening();
+        let mut scheduler = Scheduler {
+            employees: vec![
+                Employee { unavailable: vec![evening.clone()], ..employee("Alice") },
+                Employee { time_off: vec![TimeOff { day: Days::Monday, shift: None }], ..employee("Bob") },
+                employee("Carol"),
+                employee("Dan"),
+            ],
+            config: ScheduleConfig {
+                operational_days: vec![Days::Monday],
+                shifts: vec![Shifts::morning(), evening.clone()],
+                shift_min_emps: 2,
+                forbidden: vec![Assignment::new("Dan", Days::Monday, evening.clone())],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let error = scheduler.schedule_week(&scheduler.config).unwrap_err();
+        let explanation = scheduler.explain(&error);
+        assert_eq!(explanation.slot, Some((Days::Monday, evening.clone())));
+        assert_eq!((explanation.needed, explanation.eligible), (2, 1));
+        assert_eq!(explanation.excluded, vec![
+            ("Alice".to_string(), Exclusion::UnavailableShift),
+            ("Bob".to_string(), Exclusion::TimeOff),
+            ("Dan".to_string(), Exclusion::Forbidden),
+        ]);
+        assert_eq!(explanation.suggestions[0].to_string(), "Add 1 Evening-capable employee");
+        assert_eq!(explanation.suggestions[1], Suggestion::LowerStaffing { day: Days::Monday, shift: evening.clone(), to: 1 });
+
+        // A roster error is explained with the rules of its week
+        let start = Date::parse("2026-11-02").unwrap();
+        let error = scheduler.get_roster(start, start.add_days(6)).unwrap_err();
+        let explanation = scheduler.explain_roster(&error, start, start.add_days(6));
+        assert_eq!(explanation.week_of, Some(start));
+        assert_eq!(explanation.slot, Some((Days::Monday, evening)));
+
+        scheduler.employees.truncate(1);
+        let explanation = scheduler.explain(&scheduler.schedule_week(&scheduler.config).unwrap_err());
+        assert_eq!(explanation.slot, None);
+        assert_eq!(explanation.suggestions, vec![Suggestion::AddEmployees { count: 3, shift: None, skill: None }]);
+
+        // Days before the start of the roster are not blamed
+        let scheduler = Scheduler {
+            employees: vec![
+                Employee { unavailable_days: vec![Days::Monday], ..employee("Alice") },
+                Employee { unavailable_days: vec![Days::Monday], ..employee("Bob") },
+                employee("Carol"),
+                employee("Dan"),
+            ],
+            config: ScheduleConfig { shifts: vec![Shifts::morning()], shift_min_emps: 3, max_consecutive_days: Some(1), ..Default::default() },
+            solver: Box::new(BacktrackingSolver),
+        };
+        let start = Date::parse("2026-11-04").unwrap();
+        let error = scheduler.get_roster(start, start.add_days(4)).unwrap_err();
+        let explanation = scheduler.explain_roster(&error, start, start.add_days(4));
+        assert_eq!(explanation.slot, Some((Days::Wednesday, Shifts::morning())));
+        assert_eq!((explanation.needed, explanation.eligible), (3, 4));
+    }
+}
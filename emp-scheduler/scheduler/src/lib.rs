@@ -1,16 +1,35 @@
 use std::collections::HashMap;
-
 use std::fmt;
 
-const OPERATIONAL_DAYS: usize = 7;
-
-const DAY_NUM_SHIFTS: usize = 3;
-
-const SHIFT_MIN_EMPS: usize = 2;
+mod calendar;
+mod config;
+mod date;
+mod error;
+mod explain;
+mod metrics;
+mod optimize;
+mod repair;
+mod roster;
+mod schedule;
+mod score;
+mod shift;
+mod solver;
+mod validate;
 
-const EMP_MAX_DAYS: usize = 5;
-
-// const EMP_MAX_SHIFTS: usize = 1;
+pub use calendar::{Calendar, Holiday, ParseCalendarError};
+pub use config::{Assignment, RestRule, ScheduleConfig, SkillRequirement, StaffingDemand, TieBreak, Undesirable};
+pub use date::Date;
+pub use error::SchedulerError;
+pub use explain::{Exclusion, Explanation, Suggestion};
+pub use metrics::{EmployeeMetrics, Metrics};
+pub use optimize::{Budget, Improvement};
+pub use repair::Change;
+pub use roster::Roster;
+pub use schedule::{RestViolation, Schedule, ScheduleDiff};
+pub use score::{Score, Weights};
+pub use shift::{ShiftTime, Shifts};
+pub use solver::{solver_by_name, BacktrackingSolver, GreedySolver, Solver, SOLVER_NAMES};
+pub use validate::{validate, Violation};
 
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 pub enum Days {
@@ -30,6 +49,16 @@ impl Days {
             Self::Friday, Self::Saturday, Self::Sunday
         ].iter().copied()
     }
+
+    /// The following day in the same week, `None` for Sunday.
+    pub fn next_day(&self) -> Option<Days> {
+        Days::iter().skip_while(|d| d != self).nth(1)
+    }
+
+    /// The preceding day in the same week, `None` for Monday.
+    pub fn previous_day(&self) -> Option<Days> {
+        Days::iter().take_while(|d| d != self).last()
+    }
 }
 
 impl fmt::Display for Days {
@@ -48,215 +77,387 @@ impl fmt::Display for Days {
     
 }
 
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-pub enum Shifts {
-    Morning,
-    Afternoon,
-    Evening,
+/// Approved time off for a whole day or a single shift.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct TimeOff {
+    pub day: Days,
+
+    /// Shift taken off, `None` for the whole day.
+    pub shift: Option<Shifts>,
 }
 
-impl Shifts {
+impl TimeOff {
 
-    pub fn iter() -> impl Iterator<Item = Shifts> {
-        [Self::Morning, Self::Afternoon, Self::Evening].iter().copied()
+    pub fn covers(&self, day: Days, shift: &Shifts) -> bool {
+        self.day == day && self.shift.as_ref().is_none_or(|s| s == shift)
     }
 }
 
-impl fmt::Display for Shifts {
-    
+impl fmt::Display for TimeOff {
+
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::Morning => write!(f, "Morning"),
-            Self::Afternoon => write!(f, "Afternoon"),
-            Self::Evening => write!(f, "Evening"),
+        match &self.shift {
+            Some(shift) => write!(f, "{} {}", self.day, shift),
+            None => write!(f, "{}", self.day),
         }
     }
-    
 }
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+/// Weekly hours an employee is contracted to work.
+#[derive(Debug, Copy, Clone, Default, Eq, Hash, PartialEq)]
+pub struct Contract {
+    /// Hours the employee is guaranteed to be scheduled.
+    pub min_hours: usize,
+
+    /// Most hours the employee can be scheduled, `None` for no limit.
+    pub max_hours: Option<usize>,
+}
+
+impl Contract {
+
+    pub fn full_time() -> Self {
+        Self { min_hours: 0, max_hours: Some(40) }
+    }
+
+    pub fn part_time() -> Self {
+        Self { min_hours: 0, max_hours: Some(20) }
+    }
+
+    /// Checks `minutes` of work are within the maximum hours.
+    pub fn allows(&self, minutes: u32) -> bool {
+        self.max_hours.is_none_or(|max| minutes as usize <= max * 60)
+    }
+
+    /// Checks `minutes` of work reach the guaranteed hours.
+    pub fn is_met(&self, minutes: u32) -> bool {
+        minutes as usize >= self.min_hours * 60
+    }
+}
+
+impl fmt::Display for Contract {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.min_hours, self.max_hours) {
+            (0, None) => write!(f, "any hours"),
+            (min, None) => write!(f, "at least {}h", min),
+            (0, Some(max)) => write!(f, "up to {}h", max),
+            (min, Some(max)) => write!(f, "{}-{}h", min, max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
 pub struct Employee {
     pub name: String,
 
-    pub preferred_shift: Shifts,
+    /// Shifts the employee would like to work, most preferred first.
+    pub preferences: Vec<Shifts>,
+
+    /// Shifts the employee can never be scheduled for.
+    pub unavailable: Vec<Shifts>,
+
+    /// Days the employee can never be scheduled for.
+    pub unavailable_days: Vec<Days>,
+
+    /// Approved time off in the week.
+    pub time_off: Vec<TimeOff>,
+
+    /// Weekly hours the employee is contracted for.
+    pub contract: Contract,
+
+    /// Skills and roles held, such as keyholder.
+    pub skills: Vec<String>,
+
+    /// Rank used by the `Seniority` tie break, higher being favoured first.
+    pub seniority: u32,
+}
+
+impl Employee {
+
+    /// The employee's first choice of shift.
+    pub fn preferred_shift(&self) -> Option<&Shifts> {
+        self.preferences.first()
+    }
+
+    /// Position of a shift in the employee's preferences, `0` being the first choice.
+    pub fn preference_rank(&self, shift: &Shifts) -> Option<usize> {
+        self.preferences.iter().position(|s| s == shift)
+    }
+
+    pub fn can_work(&self, shift: &Shifts) -> bool {
+        !self.unavailable.contains(shift)
+    }
+
+    pub fn on_time_off(&self, day: Days, shift: &Shifts) -> bool {
+        self.time_off.iter().any(|t| t.covers(day, shift))
+    }
+
+    pub fn has_skill(&self, skill: &str) -> bool {
+        self.skills.iter().any(|s| s == skill)
+    }
+
+    /// Checks the employee can be scheduled for a shift on a day.
+    pub fn is_available(&self, day: Days, shift: &Shifts) -> bool {
+        self.can_work(shift) && !self.unavailable_days.contains(&day) && !self.on_time_off(day, shift)
+    }
 }
 
 impl fmt::Display for Employee {
 
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
-        write!(f, "{} {}", self.name, self.preferred_shift)
+        let preferences: Vec<_> = self.preferences.iter().map(|s| s.to_string()).collect();
+        write!(f, "{} {}", self.name, preferences.join(" > "))?;
+
+        if !self.unavailable.is_empty() {
+            let unavailable: Vec<_> = self.unavailable.iter().map(|s| s.to_string()).collect();
+            write!(f, " (not {})", unavailable.join(", "))?;
+        }
+        Ok(())
     }
 }
 
-#[derive(Default)]
 pub struct Scheduler {
 
     pub employees: Vec<Employee>,
+
+    pub config: ScheduleConfig,
+
+    pub solver: Box<dyn Solver>,
+}
+
+impl Default for Scheduler {
+
+    fn default() -> Self {
+        Self {
+            employees: Vec::new(),
+            config: ScheduleConfig::default(),
+            solver: Box::new(GreedySolver),
+        }
+    }
 }
 
 impl Scheduler {
 
-    pub fn get_schedule(self) -> Result<HashMap<Days, HashMap<Shifts, Vec<Employee>>>, String> {
-
-        if self.employees.len() * EMP_MAX_DAYS  < OPERATIONAL_DAYS * DAY_NUM_SHIFTS * SHIFT_MIN_EMPS {
-            // println!("Not enough employees to schedule for the entire week.");
-            return Err("Not enough employees".into());
-        }
-
-        
-        // for emp in &self.employees {
-        //     println!("Employee: {}", emp);
-        // }
-
-        let mut shifts = HashMap::<Days, HashMap::<Shifts, Vec<Employee>>>::new();
-        let mut emp_days = HashMap::<Employee, usize>::new();
-
-        /*
-        for emp in &self.employees {
-            for day in Days::iter() {
-                if EMP_MAX_DAYS > *emp_days.get(emp).unwrap_or(&0) {
-                    for shift in Shifts::iter() {
-                        if emp.preferred_shift == shift {
-                           let mut day_shifts = shifts.entry(day).or_insert(HashMap::new());
-                           let mut shift_emps = day_shifts.entry(shift).or_insert(Vec::new());
-
-                           if shift_emps.len() < SHIFT_MIN_EMPS {
-                               shift_emps.push(emp.clone());
-                               *emp_days.entry(emp.clone()).or_insert(0) += 1;
-                               break; //'day;
-                           } 
-                        }
-                    } 
-                } else {
-                    break;
-                }
-            }
+    pub fn get_schedule(&self) -> Result<Schedule, SchedulerError> {
+        self.schedule_week(&self.config)
+    }
+
+    /// Schedules every date from `start` to `end`, one Monday to Sunday week at a time.
+    ///
+    /// Operational days outside the range are left out of the first and last weeks, as
+    /// are dates the config's `calendar` closes, and holidays change the staffing of
+    /// every shift on their date. Each week is scheduled with the week before as its history, starting from the
+    /// config's `history`, and counts the weeks before in its `past_weeks` when `fairness_weeks` is set.
+    pub fn get_roster(&self, start: Date, end: Date) -> Result<Roster, SchedulerError> {
+        let mut roster = Roster::new();
+        let mut history = self.config.history.clone();
+        let mut past_weeks = self.config.past_weeks.clone();
+
+        let mut week = start.week_start();
+        while week <= end {
+            let config = ScheduleConfig { past_weeks: past_weeks.clone(), ..self.week_config(week, start, end, history) };
+            let schedule = self.schedule_week(&config)
+                .map_err(|error| SchedulerError::InWeek { week_of: week, error: Box::new(error) })?;
+            history = Some(schedule.clone());
+            self.count_past_week(&mut past_weeks, &schedule);
+            roster.insert_week(week, schedule);
+            week = week.add_days(7);
         }
 
-        for day in Days::iter() {
-            let mut day_shifts = shifts.entry(day).or_insert(HashMap::new());
-            let mut day_employees: Vec<Employee> = Vec::new();
+        Ok(roster)
+    }
+
+    /// Adds a week to the past weeks, keeping the `fairness_weeks` latest.
+    fn count_past_week(&self, past_weeks: &mut Vec<Schedule>, schedule: &Schedule) {
+        past_weeks.push(schedule.clone());
+        let excess = past_weeks.len().saturating_sub(self.config.fairness_weeks);
+        past_weeks.drain(..excess);
+    }
+
+    /// Checks every week of a roster for the dates from `start` to `end` against the
+    /// rules `get_roster` schedules them with, returning each violation with the Monday
+    /// of its week.
+    pub fn validate_roster(&self, roster: &Roster, start: Date, end: Date) -> Vec<(Date, Violation)> {
+        let mut violations = Vec::new();
+        let mut history = self.config.history.clone();
+        let empty = Schedule::new();
+
+        let mut week = start.week_start();
+        while week <= end {
+            let config = self.week_config(week, start, end, history);
+            let schedule = roster.week_of(week).unwrap_or(&empty);
+            violations.extend(validate(schedule, &self.employees, &config).into_iter().map(|v| (week, v)));
+            history = Some(schedule.clone());
+            week = week.add_days(7);
+        }
+        violations
+    }
 
-            for shift in Shifts::iter() {
-                let mut shift_emps = day_shifts.entry(shift).or_insert(Vec::new());
+    /// Rules for the week starting on `week` of a roster from `start` to `end`.
+    fn week_config(&self, week: Date, start: Date, end: Date, history: Option<Schedule>) -> ScheduleConfig {
+        ScheduleConfig {
+            operational_days: self.config.operational_days.iter().copied()
+                .filter(|&day| (start..=end).contains(&week.in_week(day)))
+                .filter(|&day| !self.config.calendar.is_closed(week.in_week(day)))
+                .collect(),
+            demand: self.holiday_demand(week),
+            pinned: self.config.pinned.iter().filter(|pin| pin.in_week(week)).map(|pin| Assignment { week: None, ..pin.clone() }).collect(),
+            forbidden: self.config.forbidden.iter().filter(|f| f.in_week(week)).map(|f| Assignment { week: None, ..f.clone() }).collect(),
+            history,
+            ..self.config.clone()
+        }
+    }
 
-                for shift_emp in shift_emps.iter() {
-                    day_employees.push(shift_emp.clone());
+    /// Staffing for the week starting on `week`, with holidays overriding every shift on their date.
+    fn holiday_demand(&self, week: Date) -> HashMap<(Days, Shifts), StaffingDemand> {
+        let mut demand = self.config.demand.clone();
+        for (date, holiday) in self.config.calendar.between(week, week.add_days(6)) {
+            if let Some(staffing) = holiday.demand {
+                for shift in &self.config.shifts {
+                    demand.insert((date.weekday(), shift.clone()), staffing);
                 }
             }
-            for shift in Shifts::iter() {
-                let mut shift_emps = day_shifts.entry(shift).or_insert(Vec::new());
+        }
+        demand
+    }
 
-                if shift_emps.len() < SHIFT_MIN_EMPS {
-                    let mut available_employees: Vec<_> = self.employees.iter().filter(|e| {
-                       *emp_days.get(e).unwrap_or(&0) < EMP_MAX_DAYS && !day_employees.contains(e)
-                    }).collect();
+    fn schedule_week(&self, config: &ScheduleConfig) -> Result<Schedule, SchedulerError> {
+        config.check_demand()?;
 
-                    if available_employees.len() + shift_emps.len() < SHIFT_MIN_EMPS {
-                        // println!("Not enough employees to schedule for {} - {}", day, shift);
-                        return Err("Not enough employees".into());
-                    }
-                    
-                    let new_emps = available_employees.into_iter()
-                        .take(SHIFT_MIN_EMPS - shift_emps.len()).cloned().collect::<Vec<_>>();
+        let required = config.required_shifts();
+        let available = self.employees.len() * config.employee_capacity();
+        if available < required {
+            return Err(SchedulerError::InsufficientCapacity { required, available });
+        }
 
-                    for emp in new_emps.iter() {
-                        day_employees.push(emp.clone());
-                        *emp_days.entry(emp.clone()).or_insert(0) += 1;
+        self.check_pins(config)?;
+        self.check_availability(config)?;
 
-                    }
-                    shift_emps.extend(new_emps);
-                }
+        self.solver.solve(&config.tie_break.order(&self.employees, config.seed), config)
+    }
+
+    /// Checks every pinned employee can work their shift, and works no more than one shift a day
+    /// and `emp_max_days` days, within the rest, working days and contract rules, and that no
+    /// shift has more pinned employees than its maximum.
+    fn check_pins(&self, config: &ScheduleConfig) -> Result<(), SchedulerError> {
+        for pin in config.weekly_pins() {
+            let conflict = || SchedulerError::PinConflict { employee: pin.employee.clone(), day: pin.day, shift: pin.shift.clone() };
+            let employee = self.employees.iter().find(|e| e.name == pin.employee).ok_or_else(conflict)?;
+
+            let mut days: Vec<_> = config.weekly_pins().filter(|p| p.employee == pin.employee).map(|p| (p.day, &p.shift)).collect();
+            days.sort_by_key(|&(day, shift)| (Days::iter().position(|d| d == day), shift));
+            days.dedup();
+            let same_day = days.iter().filter(|&&(day, _)| day == pin.day).count();
+
+            if !config.operational_days.contains(&pin.day) || !config.shifts.contains(&pin.shift)
+                || !config.can_assign(employee, pin.day, &pin.shift) || same_day > 1 || days.len() > config.emp_max_days {
+                return Err(conflict());
+            }
+
+            // Pinned the day before, or carried over from the history into Monday
+            let previous = config.previous_day(pin.day)
+                .and_then(|previous| days.iter().find(|&&(day, _)| day == previous).map(|&(_, shift)| shift))
+                .or(if pin.day == Days::Monday { config.carried_shift(employee) } else { None });
+            let work_days: Vec<_> = days.iter().map(|&(day, _)| day).collect();
+            let minutes = days.iter().map(|(_, shift)| shift.duration_minutes()).sum();
+            let pinned = config.pinned_employees(&self.employees, pin.day, &pin.shift).len();
+
+            if previous.is_some_and(|previous| !config.rest_allows(previous, &pin.shift))
+                || !config.allows_work_days_for(employee, &work_days) || !employee.contract.allows(minutes)
+                || !config.demand(pin.day, &pin.shift).allows(pinned) {
+                return Err(conflict());
             }
         }
+        Ok(())
+    }
 
-        println!("Shifts: {:?}", shifts);
-        println!("Emps: {:?}", emp_days);
-        */
+    /// Checks every shift has enough employees, and enough with each required skill,
+    /// who are not unavailable or on time off.
+    fn check_availability(&self, config: &ScheduleConfig) -> Result<(), SchedulerError> {
+        for &day in &config.operational_days {
+            for shift in &config.shifts {
+                let needed = config.demand(day, shift).min;
+                let (available, unavailable): (Vec<_>, Vec<_>) = self.employees.iter()
+                    .partition(|e| config.can_assign(e, day, shift));
 
-        
-        for day in Days::iter() {
-            // let employees = &self.employees.clone();
-            let mut day_shifts = HashMap::<Shifts, Vec<Employee>>::new();
-            let mut day_employees: Vec<Employee> = Vec::new();
-
-            for shift in Shifts::iter() {
-                let mut available_employees: Vec<_> = self.employees.iter().filter(|e| {
-                   *emp_days.get(e).unwrap_or(&0) < EMP_MAX_DAYS && !day_employees.contains(e) && e.preferred_shift == shift
-                }).collect();
-
-                // Sort the list by the least scheduled employees
-                available_employees.sort_by_key(|e| *emp_days.get(e).unwrap_or(&0));
-                
-                let mut shift_employees: Vec<_> = available_employees.into_iter().take(SHIFT_MIN_EMPS).cloned().collect();
-                if shift_employees.len() < SHIFT_MIN_EMPS {
-                    let mut available_employees: Vec<_> = self.employees.iter().filter(|e| {
-                        *emp_days.get(e).unwrap_or(&0) < EMP_MAX_DAYS && !shift_employees.contains(e) && !day_employees.contains(e)
-                    }).collect();
-
-                    // Sort the list by the least scheduled employees
-                    available_employees.sort_by_key(|e| *emp_days.get(e).unwrap_or(&0));
-                    
-                    if available_employees.len() + shift_employees.len() < SHIFT_MIN_EMPS {
-                        // println!("Not enough employees to schedule for {} - {}", day, shift);
-                        // println!("State: {:?}", shifts);
-                        // println!("Employees: {:?}", emp_days);
-                        return Err("Not enough employees".into());
+                for required in config.required_skills(day, shift) {
+                    let found = available.iter().filter(|e| e.has_skill(&required.skill)).count();
+                    if found < required.count {
+                        return Err(SchedulerError::MissingSkill {
+                            day,
+                            shift: shift.clone(),
+                            skill: required.skill.clone(),
+                            needed: required.count,
+                            found,
+                        });
                     }
-
-                    shift_employees.extend(available_employees.into_iter()
-                        .take(SHIFT_MIN_EMPS - shift_employees.len()).cloned().collect::<Vec<_>>());
                 }
-                for e in &shift_employees {
-                    *emp_days.entry(e.clone()).or_insert(0) += 1;
-                    day_employees.push(e.clone());
+
+                if available.len() < needed && !unavailable.is_empty() {
+                    return Err(SchedulerError::TimeOffConflict {
+                        day,
+                        shift: shift.clone(),
+                        needed,
+                        found: available.len(),
+                        unavailable: unavailable.iter().map(|e| e.name.clone()).collect(),
+                    });
                 }
-                day_shifts.insert(shift, shift_employees);
-                // println!("Day: {}, Shift: {}", day, shift);
             }
-            shifts.insert(day, day_shifts);
         }
-        
 
-        Ok(shifts)
+        Ok(())
     }
-    
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::rng;
-    use rand::prelude::IteratorRandom;
+    use std::env;
+    use rand::rngs::StdRng;
+    use rand::prelude::{IteratorRandom, SeedableRng};
 
-    fn get_random_shift() -> Shifts {
-        let mut rng = rng();
-        Shifts::iter().choose(&mut rng).unwrap()
+    /// Seed for the random employees of a test, taken from `SCHEDULER_TEST_SEED` to replay
+    /// a failing test.
+    fn test_seed() -> u64 {
+        let seed = env::var("SCHEDULER_TEST_SEED").ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(rand::random);
+        // Shown with the output of a failing test
+        println!("Replay with SCHEDULER_TEST_SEED={}", seed);
+        seed
     }
 
-    fn create_employees(count: usize) -> Vec<Employee> {
+    /// An employee with only a name, shared by the tests of every module.
+    pub(crate) fn employee(name: &str) -> Employee {
+        Employee { name: name.into(), ..Default::default() }
+    }
+
+    fn create_employees(count: usize, seed: u64) -> Vec<Employee> {
+        let mut rng = StdRng::seed_from_u64(seed);
         (0..count).map(|i| {
             Employee {
-                name: format!("Emp {}", i),
-                preferred_shift: get_random_shift(),
+                preferences: vec![Shifts::standard().into_iter().choose(&mut rng).unwrap()],
+                ..employee(&format!("Emp {}", i))
             }
         }).collect()
     }
 
     #[test]
     fn test_not_enough_employees() {
-        let emps = create_employees(8);
-        let mut scheduler = Scheduler { employees: emps };
+        let seed = test_seed();
+        let emps = create_employees(8, seed);
+        let scheduler = Scheduler { employees: emps, ..Default::default() };
 
         let result = scheduler.get_schedule();
         assert!(result.is_err());
-        assert_eq!(result.err(), Some("Not enough employees".into()));
+        assert_eq!(result.err(), Some(SchedulerError::InsufficientCapacity { required: 42, available: 40 }));
     }
 
     #[test]
     fn test_schedule_possible() {
-        let emps = create_employees(10);
-        let mut scheduler = Scheduler { employees: emps };
+        let seed = test_seed();
+        let emps = create_employees(10, seed);
+        let scheduler = Scheduler { employees: emps, ..Default::default() };
 
         let result = scheduler.get_schedule();
         assert!(result.is_ok());
@@ -264,36 +465,778 @@ mod tests {
 
     #[test]
     fn test_rules() {
-        let emps = create_employees(10);
+        let seed = test_seed();
+        let emps = create_employees(10, seed);
 
-        let mut scheduler = Scheduler { employees: emps };
+        let scheduler = Scheduler { employees: emps, ..Default::default() };
+        let config = scheduler.config.clone();
+        let scheduler_employees = scheduler.employees.clone();
 
         let schedule = scheduler.get_schedule().expect("Schedule should be created");
 
-        let mut emp_shifts = HashMap::new();
+        for (day, shift, employees) in schedule.iter() {
+            assert!(employees.len() >= config.demand(day, shift).min, "Shift size too small, {}, {}", day, shift);
+        }
+
+        for day in Days::iter() {
+            let mut day_employees: Vec<_> = schedule.employees_on(day).iter().map(|e| e.name.clone()).collect();
+
+            let day_emp_count = day_employees.len();
+            day_employees.sort();
+            day_employees.dedup();
+
+            assert_eq!(day_emp_count, day_employees.len(), "Duplicate shift on {}", day);
+        }
+
+        for emp in &scheduler_employees {
+            let count = schedule.days_worked(emp);
+            assert!(count <= config.emp_max_days, "Employee {} worked too many days {}", emp.name, count);
+            assert_eq!(count, schedule.shifts_for(emp).len());
+        }
 
-        for (day, day_shifts) in &schedule {
-           let mut day_employees = Vec::new();
+        assert_eq!(validate(&schedule, &scheduler_employees, &config), vec![]);
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let seed = test_seed();
+        let config = ScheduleConfig { shift_min_emps: 3, emp_max_days: 6, ..Default::default() };
 
-           for (shift, employees) in day_shifts {
-               assert!(employees.len() >= SHIFT_MIN_EMPS, "Shift size too small, {}, {}", day, shift);
+        let scheduler = Scheduler { employees: create_employees(10, seed), config: config.clone(), ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::InsufficientCapacity { required: 63, available: 60 }));
 
-               for emp in employees {
-                   *emp_shifts.entry(emp.name.clone()).or_insert(0) += 1;
+        let scheduler = Scheduler { employees: create_employees(15, seed), config: config.clone(), ..Default::default() };
+        let schedule = scheduler.get_schedule().expect("Schedule should be created");
 
-                   day_employees.push(emp.name.clone());
-               }
-           }
+        assert_eq!(schedule.iter().count(), 21);
+        for (_, _, employees) in schedule.iter() {
+            assert_eq!(employees.len(), config.shift_min_emps);
+        }
+    }
 
-           let day_emp_count = day_employees.len();
-           day_employees.sort();
-           day_employees.dedup();
+    #[test]
+    fn test_invalid_demand() {
+        let seed = test_seed();
+        let config = ScheduleConfig { shift_min_emps: 3, shift_max_emps: Some(2), ..Default::default() };
+        let error = SchedulerError::InvalidDemand { day: Days::Monday, shift: Shifts::morning(), min: 3, max: 2 };
 
-           assert_eq!(day_emp_count, day_employees.len(), "Duplicate shift on {}", day);
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            assert_eq!(solver.solve(&create_employees(10, seed), &config), Err(error.clone()));
+            let scheduler = Scheduler { employees: create_employees(10, seed), config: config.clone(), solver };
+            assert_eq!(scheduler.get_schedule(), Err(error.clone()));
         }
+    }
+
+    /// Roster where the greedy pass runs out on Sunday although everyone
+    /// can be given exactly one day off.
+    fn tight_roster() -> (Vec<Employee>, ScheduleConfig) {
+        let config = ScheduleConfig { emp_max_days: 6, ..Default::default() };
+        let prefs = [Shifts::afternoon(), Shifts::afternoon(), Shifts::morning(), Shifts::morning(),
+            Shifts::morning(), Shifts::morning(), Shifts::morning()];
+        let employees = prefs.into_iter().enumerate().map(|(i, shift)| {
+            Employee { preferences: vec![shift], ..employee(&format!("Emp {}", i)) }
+        }).collect();
+
+        (employees, config)
+    }
+
+    #[test]
+    fn test_shift_understaffed() {
+        let (employees, config) = tight_roster();
+        let scheduler = Scheduler { employees, config, ..Default::default() };
+
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::ShiftUnderstaffed {
+            day: Days::Sunday,
+            shift: Shifts::evening(),
+            needed: 2,
+            found: 1,
+        }));
+    }
+
+    #[test]
+    fn test_backtracking_finds_schedule() {
+        let (employees, config) = tight_roster();
+        let scheduler = Scheduler { employees: employees.clone(), config, solver: Box::new(BacktrackingSolver) };
+
+        let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+        for (day, shift, assigned) in schedule.iter() {
+            assert_eq!(assigned.len(), 2, "Shift size wrong, {}, {}", day, shift);
+        }
+        for day in Days::iter() {
+            assert_eq!(schedule.employees_on(day).len(), 6, "Duplicate shift on {}", day);
+        }
+        for emp in &employees {
+            assert_eq!(schedule.days_worked(emp), 6);
+        }
+    }
+
+    #[test]
+    fn test_backtracking_random_rosters() {
+        let seed = test_seed();
+        for count in [10, 12, 15] {
+            let scheduler = Scheduler { employees: create_employees(count, seed), solver: Box::new(BacktrackingSolver), ..Default::default() };
+            let config = scheduler.config.clone();
+            let employees = scheduler.employees.clone();
+
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+            assert_eq!(schedule.iter().count(), 21);
+            for emp in &employees {
+                assert!(schedule.days_worked(emp) <= config.emp_max_days);
+            }
+        }
+    }
+
+    #[test]
+    fn test_backtracking_prunes_day_patterns() {
+        let seed = test_seed();
+        // At most four days a week fit runs of two days with three days off in a row
+        let config = ScheduleConfig {
+            max_consecutive_days: Some(2),
+            min_days_off_in_a_row: Some(3),
+            rest_rules: vec![RestRule::no_morning_after_evening()],
+            ..Default::default()
+        };
+        for count in [9, 10] {
+            let scheduler = Scheduler { employees: create_employees(count, seed), config: config.clone(), solver: Box::new(BacktrackingSolver) };
+            assert_eq!(scheduler.get_schedule(), Err(SchedulerError::Infeasible));
+        }
+
+        let (employees, config) = tight_roster();
+        let config = ScheduleConfig { search_limit: Some(5), ..config };
+        let scheduler = Scheduler { employees, config, solver: Box::new(BacktrackingSolver) };
+        assert_eq!(scheduler.get_schedule(), Err(SchedulerError::SearchLimitReached { tried: 5 }));
+    }
+
+    #[test]
+    fn test_custom_solver() {
+        let seed = test_seed();
+        struct FirstEmployees;
+
+        impl Solver for FirstEmployees {
+            fn name(&self) -> &str {
+                "first"
+            }
+
+            fn solve(&self, employees: &[Employee], config: &ScheduleConfig) -> Result<Schedule, SchedulerError> {
+                let mut schedule = Schedule::new();
+                schedule.insert(config.operational_days[0], config.shifts[0].clone(), employees[..1].to_vec());
+                Ok(schedule)
+            }
+        }
+
+        let emps = create_employees(10, seed);
+        let scheduler = Scheduler { employees: emps.clone(), solver: Box::new(FirstEmployees), ..Default::default() };
+
+        let schedule = scheduler.get_schedule().expect("Schedule should be created");
+        assert_eq!(schedule.iter().count(), 1);
+        assert_eq!(schedule.assignments(Days::Monday, &Shifts::morning()), &emps[..1]);
+    }
+
+    #[test]
+    fn test_unavailable_shifts() {
+        let seed = test_seed();
+        // Nobody can work evenings except four employees who prefer them
+        let mut emps = create_employees(12, seed);
+        for emp in emps.iter_mut().take(4) {
+            emp.preferences = vec![Shifts::evening()];
+        }
+        for emp in emps.iter_mut().skip(4) {
+            emp.preferences = vec![Shifts::evening(), Shifts::morning()];
+            emp.unavailable = vec![Shifts::evening()];
+        }
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let config = ScheduleConfig { emp_max_days: 7, ..Default::default() };
+            let scheduler = Scheduler { employees: emps.clone(), config, solver };
+
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+            for (day, shift, employees) in schedule.iter() {
+                for emp in employees {
+                    assert!(emp.can_work(shift), "{} cannot work {} {}", emp.name, day, shift);
+                }
+            }
+        }
+
+        // Two evening workers cannot cover every evening working five days each
+        for emp in emps.iter_mut().take(4).skip(2) {
+            emp.unavailable = vec![Shifts::evening()];
+        }
+        let scheduler = Scheduler { employees: emps, solver: Box::new(BacktrackingSolver), ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::Infeasible));
+    }
+
+    #[test]
+    fn test_ranked_preferences() {
+        let emps: Vec<_> = (0..12).map(|i| Employee {
+            preferences: vec![Shifts::standard()[i % 3].clone(), Shifts::standard()[(i + 1) % 3].clone()],
+            ..employee(&format!("Emp {}", i))
+        }).collect();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: emps.clone(), solver, ..Default::default() };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            // Everyone prefers an even split, so every shift goes to someone who asked for it
+            for (_, shift, employees) in schedule.iter() {
+                for emp in employees {
+                    assert!(emp.preference_rank(shift).is_some(), "{} not ranked for {}", emp.name, shift);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_off() {
+        let seed = test_seed();
+        let mut emps = create_employees(12, seed);
+        emps[0].unavailable_days = vec![Days::Monday, Days::Tuesday];
+        emps[1].time_off = vec![TimeOff { day: Days::Wednesday, shift: None }];
+        emps[2].time_off = vec![TimeOff { day: Days::Friday, shift: Some(Shifts::evening()) }];
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: emps.clone(), solver, ..Default::default() };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            for (day, shift, employees) in schedule.iter() {
+                for emp in employees {
+                    assert!(emp.is_available(day, shift), "{} is not available {} {}", emp.name, day, shift);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_off_conflict() {
+        let seed = test_seed();
+        let mut emps = create_employees(12, seed);
+        for emp in emps.iter_mut().skip(1) {
+            emp.time_off = vec![TimeOff { day: Days::Thursday, shift: Some(Shifts::afternoon()) }];
+        }
+
+        let scheduler = Scheduler { employees: emps, ..Default::default() };
+        match scheduler.get_schedule() {
+            Err(SchedulerError::TimeOffConflict { day, shift, needed, found, unavailable }) => {
+                assert_eq!((day, shift), (Days::Thursday, Shifts::afternoon()));
+                assert_eq!((needed, found), (2, 1));
+                assert_eq!(unavailable.len(), 11);
+            },
+            other => panic!("Expected time off conflict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rest_rules() {
+        let seed = test_seed();
+        let config = ScheduleConfig { rest_rules: vec![RestRule::no_morning_after_evening()], ..Default::default() };
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: create_employees(12, seed), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            assert_eq!(schedule.rest_violations(&config), vec![]);
+        }
+
+        // Everyone has to work six days, so only the backtracking solver can place the evenings
+        let (employees, mut config) = tight_roster();
+        config.emp_max_days = 7;
+        config.rest_rules = vec![RestRule::no_morning_after_evening()];
+        let scheduler = Scheduler { employees, config: config.clone(), solver: Box::new(BacktrackingSolver) };
+        let schedule = scheduler.get_schedule().expect("Schedule should be created");
+        assert_eq!(schedule.rest_violations(&config), vec![]);
+    }
+
+    #[test]
+    fn test_consecutive_days() {
+        let seed = test_seed();
+        let config = ScheduleConfig {
+            max_consecutive_days: Some(3),
+            min_days_off_in_a_row: Some(2),
+            ..Default::default()
+        };
+
+        // The greedy pass does not plan ahead for days off, so give it more room
+        for (solver, count) in [(Box::new(GreedySolver) as Box<dyn Solver>, 16), (Box::new(BacktrackingSolver), 12)] {
+            let employees = create_employees(count, seed);
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            for emp in &employees {
+                let days = schedule.days_for(emp);
+                assert!(config.allows_work_days(&days), "Employee {} works {:?}", emp.name, days);
+            }
+        }
+
+        // Across the Sunday to Monday boundary of a repeating week
+        let config = ScheduleConfig { max_consecutive_days: Some(4), wrap_weeks: true, ..Default::default() };
+        let employees = create_employees(11, seed);
+        let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver: Box::new(BacktrackingSolver) };
+        let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+        for emp in &employees {
+            let days = schedule.days_for(emp);
+            assert!(config.allows_work_days(&days), "Employee {} works {:?}", emp.name, days);
+        }
+    }
+
+    #[test]
+    fn test_staffing_demand() {
+        let seed = test_seed();
+        let mut config = ScheduleConfig::default();
+        config.demand.insert((Days::Saturday, Shifts::evening()), StaffingDemand { min: 4, max: Some(5) });
+        config.demand.insert((Days::Monday, Shifts::morning()), StaffingDemand { min: 1, max: Some(1) });
+        assert_eq!(config.required_shifts(), 43);
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: create_employees(12, seed), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            for (day, shift, employees) in schedule.iter() {
+                let demand = config.demand(day, shift);
+                assert!(employees.len() >= demand.min && demand.allows(employees.len()), "Wrong staffing {} {}", day, shift);
+            }
+            assert_eq!(schedule.assignments(Days::Saturday, &Shifts::evening()).len(), 4);
+            assert_eq!(schedule.assignments(Days::Monday, &Shifts::morning()).len(), 1);
+        }
+
+        // The pre-check counts the extra Saturday evening staff
+        let scheduler = Scheduler { employees: create_employees(8, seed), config, ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::InsufficientCapacity { required: 43, available: 40 }));
+    }
+
+    #[test]
+    fn test_custom_shifts() {
+        let lunch = Shifts::parse("Lunch=10:00-14:00").unwrap();
+        let night = Shifts::parse("Night=22:00-06:00/30").unwrap();
+        let config = ScheduleConfig {
+            shifts: vec![Shifts::morning(), lunch.clone(), night.clone()],
+            min_rest_hours: Some(11),
+            ..Default::default()
+        };
+
+        let employees: Vec<_> = (0..12).map(|i| Employee {
+            preferences: vec![config.shifts[i % 3].clone()],
+            ..employee(&format!("Emp {}", i))
+        }).collect();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            let monday: Vec<_> = schedule.iter().filter(|(day, _, _)| *day == Days::Monday).map(|(_, shift, _)| shift).collect();
+            assert_eq!(monday, vec![&Shifts::morning(), &lunch, &night]);
+
+            // Nobody opens the morning after finishing a night at 06:00
+            assert_eq!(schedule.rest_violations(&config), vec![]);
+            for emp in &employees {
+                let minutes: u32 = schedule.shifts_for(emp).iter().map(|(_, shift)| shift.duration_minutes()).sum();
+                assert_eq!(schedule.hours_for(emp), minutes as f32 / 60.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contract_hours() {
+        let seed = test_seed();
+        let roster = |count| {
+            let mut emps = create_employees(count, seed);
+            for emp in emps.iter_mut().take(4) {
+                emp.contract = Contract::part_time();
+            }
+            for emp in emps.iter_mut().skip(4).take(2) {
+                emp.contract = Contract { min_hours: 40, ..Contract::full_time() };
+            }
+            emps
+        };
+
+        // The greedy pass does not save hours for later in the week, so give it more room
+        for (solver, count) in [(Box::new(GreedySolver) as Box<dyn Solver>, 14), (Box::new(BacktrackingSolver), 12)] {
+            let emps = roster(count);
+            let scheduler = Scheduler { employees: emps.clone(), solver, ..Default::default() };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            for emp in &emps {
+                let minutes = schedule.minutes_for(emp);
+                assert!(emp.contract.allows(minutes) && emp.contract.is_met(minutes),
+                    "{} works {} hours on a {} contract", emp.name, schedule.hours_for(emp), emp.contract);
+            }
+        }
+
+        // Five eight hour days cannot reach 48 hours
+        let mut emps = roster(14);
+        emps[4].contract.min_hours = 48;
+        let scheduler = Scheduler { employees: emps.clone(), ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::ContractHoursUnmet {
+            employee: "Emp 4".into(),
+            min_hours: 48,
+            scheduled_minutes: 40 * 60,
+        }));
+
+        let scheduler = Scheduler { employees: emps, solver: Box::new(BacktrackingSolver), ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::Infeasible));
+        assert_eq!(Contract { min_hours: 20, max_hours: Some(40) }.to_string(), "20-40h");
+    }
+
+    #[test]
+    fn test_skills() {
+        let seed = test_seed();
+        let mut emps = create_employees(12, seed);
+        for (i, emp) in emps.iter_mut().enumerate() {
+            emp.skills = vec![if i % 2 == 0 { "keyholder" } else { "register" }.into()];
+        }
+
+        let mut config = ScheduleConfig {
+            shift_skills: vec![SkillRequirement::new("keyholder", 1), SkillRequirement::new("register", 1)],
+            ..Default::default()
+        };
+        config.skill_demand.insert((Days::Saturday, Shifts::evening()), vec![SkillRequirement::new("keyholder", 2)]);
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: emps.clone(), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            for (day, shift, employees) in schedule.iter() {
+                for required in config.required_skills(day, shift) {
+                    let skilled = employees.iter().filter(|e| e.has_skill(&required.skill)).count();
+                    assert!(skilled >= required.count, "Missing {} on {} {}", required.skill, day, shift);
+                }
+            }
+        }
+
+        // The only keyholder is off on Wednesday
+        for emp in emps.iter_mut().skip(2).step_by(2) {
+            emp.skills.clear();
+        }
+        emps[0].unavailable_days = vec![Days::Wednesday];
+        let scheduler = Scheduler { employees: emps, config, ..Default::default() };
+        assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::MissingSkill {
+            day: Days::Wednesday,
+            shift: Shifts::morning(),
+            skill: "keyholder".into(),
+            needed: 1,
+            found: 0,
+        }));
+    }
+
+    #[test]
+    fn test_roster() {
+        let seed = test_seed();
+        let start = Date::parse("2026-11-04").unwrap();
+        let end = Date::parse("2026-11-17").unwrap();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let employees = create_employees(12, seed);
+            let scheduler = Scheduler { employees: employees.clone(), solver, ..Default::default() };
+            let roster = scheduler.get_roster(start, end).expect("Roster should be created");
+
+            assert_eq!(roster.dates(), start.range_to(end).collect::<Vec<_>>());
+            assert_eq!(roster.iter().count(), 14 * 3);
+            assert_eq!(scheduler.validate_roster(&roster, start, end), vec![]);
+            assert_eq!(roster.weeks().count(), 3);
+            for (week, schedule) in roster.weeks() {
+                for emp in &employees {
+                    assert!(schedule.days_worked(emp) <= 5, "{} works too much in week of {}", emp.name, week);
+                }
+            }
+        }
+
+        // The short first week fits but the first full week does not
+        let scheduler = Scheduler { employees: create_employees(8, seed), ..Default::default() };
+        assert_eq!(scheduler.get_roster(start, end).err(), Some(SchedulerError::InWeek {
+            week_of: Date::parse("2026-11-09").unwrap(),
+            error: Box::new(SchedulerError::InsufficientCapacity { required: 42, available: 40 }),
+        }));
+    }
+
+    #[test]
+    fn test_holidays() {
+        let seed = test_seed();
+        let christmas_eve = Date::parse("2026-12-24").unwrap();
+        let christmas = Date::parse("2026-12-25").unwrap();
+        let boxing_day = Date::parse("2026-12-26").unwrap();
+        let mut calendar = Calendar::new();
+        calendar.insert(christmas_eve, Holiday::with_demand("Christmas Eve", StaffingDemand { min: 1, max: Some(1) }));
+        calendar.insert(christmas, Holiday::closed("Christmas Day"));
+        calendar.insert(boxing_day, Holiday::with_demand("Boxing Day", StaffingDemand { min: 3, max: Some(3) }));
+        let config = ScheduleConfig { calendar, ..Default::default() };
+
+        let start = Date::parse("2026-12-21").unwrap();
+        let end = Date::parse("2026-12-27").unwrap();
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: create_employees(12, seed), config: config.clone(), solver };
+            let roster = scheduler.get_roster(start, end).expect("Roster should be created");
+
+            assert_eq!(roster.dates().len(), 6);
+            assert_eq!(scheduler.validate_roster(&roster, start, end), vec![]);
+            assert!(!roster.dates().contains(&christmas));
+            for shift in Shifts::standard() {
+                assert_eq!(roster.assignments(christmas_eve, &shift).len(), 1);
+                assert!(roster.assignments(christmas, &shift).is_empty());
+                assert_eq!(roster.assignments(boxing_day, &shift).len(), 3);
+                assert_eq!(roster.assignments(end, &shift).len(), 2);
+            }
+        }
+
+        // Someone working on the closed date of an edited roster
+        let scheduler = Scheduler { employees: create_employees(12, seed), config, ..Default::default() };
+        let mut roster = scheduler.get_roster(start, end).expect("Roster should be created");
+        let mut schedule = roster.week_of(start).expect("Week is scheduled").clone();
+        schedule.insert(Days::Friday, Shifts::morning(), vec![scheduler.employees[0].clone()]);
+        roster.insert_week(start, schedule);
+        assert!(scheduler.validate_roster(&roster, start, end)
+            .contains(&(start, Violation::NotOperational { day: Days::Friday, shift: Shifts::morning() })));
+    }
+
+    #[test]
+    fn test_pinned() {
+        let seed = test_seed();
+        let mut employees = create_employees(12, seed);
+        employees[5].unavailable_days = vec![Days::Sunday];
+
+        let mut pinned = vec![Assignment::new("Emp 0", Days::Friday, Shifts::evening())];
+        // Every weekday, so Emp 4 cannot be given the weekend as well
+        for day in Days::iter().take(5) {
+            pinned.push(Assignment::new("Emp 4", day, Shifts::morning()));
+        }
+        let forbidden: Vec<_> = Shifts::standard().into_iter()
+            .map(|shift| Assignment::new("Emp 2", Days::Saturday, shift))
+            .collect();
+        let config = ScheduleConfig { pinned, forbidden, ..Default::default() };
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            assert!(schedule.assignments(Days::Friday, &Shifts::evening()).contains(&employees[0]));
+            assert_eq!(schedule.shifts_for(&employees[4]), Days::iter().take(5).map(|day| (day, Shifts::morning())).collect::<Vec<_>>());
+            assert!(!schedule.employees_on(Days::Saturday).contains(&&employees[2]));
+        }
+
+        // Pinned on a day off, or pinned without being on the roster
+        for (name, day) in [("Emp 5", Days::Sunday), ("Nobody", Days::Monday)] {
+            let pinned = vec![Assignment::new(name, day, Shifts::morning())];
+            let scheduler = Scheduler { employees: employees.clone(), config: ScheduleConfig { pinned, ..Default::default() }, ..Default::default() };
+            assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::PinConflict {
+                employee: name.into(),
+                day,
+                shift: Shifts::morning(),
+            }));
+        }
+
+        // Pins breaking the rest, contract, consecutive days or staffing rules
+        let evening_then_morning = vec![
+            Assignment::new("Emp 0", Days::Monday, Shifts::evening()),
+            Assignment::new("Emp 0", Days::Tuesday, Shifts::morning()),
+        ];
+        let mut contracted = employees.clone();
+        contracted[0].contract = Contract { min_hours: 0, max_hours: Some(8) };
+        let cases = [
+            (employees.clone(), ScheduleConfig {
+                pinned: evening_then_morning.clone(),
+                rest_rules: vec![RestRule::no_morning_after_evening()],
+                ..Default::default()
+            }, Days::Tuesday, Shifts::morning()),
+            (employees.clone(), ScheduleConfig {
+                pinned: evening_then_morning.clone(),
+                min_rest_hours: Some(11),
+                ..Default::default()
+            }, Days::Tuesday, Shifts::morning()),
+            (contracted, ScheduleConfig { pinned: evening_then_morning, ..Default::default() }, Days::Monday, Shifts::evening()),
+            (employees.clone(), ScheduleConfig {
+                pinned: Days::iter().take(3).map(|day| Assignment::new("Emp 0", day, Shifts::morning())).collect(),
+                max_consecutive_days: Some(2),
+                ..Default::default()
+            }, Days::Monday, Shifts::morning()),
+            (employees.clone(), ScheduleConfig {
+                pinned: (0..3).map(|i| Assignment::new(&format!("Emp {}", i), Days::Monday, Shifts::morning())).collect(),
+                shift_max_emps: Some(2),
+                ..Default::default()
+            }, Days::Monday, Shifts::morning()),
+        ];
+        for (employees, config, day, shift) in cases {
+            for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+                let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+                assert_eq!(scheduler.get_schedule().err(), Some(SchedulerError::PinConflict {
+                    employee: "Emp 0".into(),
+                    day,
+                    shift: shift.clone(),
+                }));
+            }
+        }
+
+        // Dated pins only apply to their own week of a roster
+        let friday = Date::parse("2026-11-06").unwrap();
+        let config = ScheduleConfig {
+            pinned: vec![Assignment::on("Emp 5", friday, Shifts::evening())],
+            forbidden: vec![Assignment::on("Emp 5", friday.add_days(7), Shifts::evening())],
+            ..Default::default()
+        };
+        let scheduler = Scheduler { employees: employees.clone(), config, solver: Box::new(BacktrackingSolver) };
+        let roster = scheduler.get_roster(friday.week_start(), friday.add_days(9)).expect("Roster should be created");
+        assert!(roster.assignments(friday, &Shifts::evening()).contains(&employees[5]));
+        assert!(!roster.assignments(friday.add_days(7), &Shifts::evening()).contains(&employees[5]));
+    }
+
+    #[test]
+    fn test_repair() {
+        let seed = test_seed();
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let employees = create_employees(12, seed);
+            let mut scheduler = Scheduler { employees: employees.clone(), solver, ..Default::default() };
+            let previous = scheduler.schedule_week(&scheduler.config).expect("Schedule should be created");
+
+            // Nothing changed, so nothing moves
+            let (schedule, diff) = scheduler.repair(&previous).expect("Schedule should be repaired");
+            assert!(diff.is_empty());
+            assert_eq!(schedule, previous);
+
+            // Emp 0 calls in sick for the week
+            let sick_days = previous.days_for(&employees[0]);
+            scheduler.apply(Change::RemoveEmployee("Emp 0".into()));
+            let (schedule, diff) = scheduler.repair(&previous).expect("Schedule should be repaired");
+
+            assert!(schedule.days_for(&employees[0]).is_empty());
+            for (day, shift, assigned) in schedule.iter() {
+                assert!(assigned.len() >= 2, "{} {} is understaffed", day, shift);
+            }
+            assert_eq!(diff.removed.iter().filter(|(_, _, e)| e.name == "Emp 0").count(), sick_days.len());
+            assert!(diff.added.iter().chain(&diff.removed).all(|(day, _, _)| sick_days.contains(day)),
+                "Only the days Emp 0 worked should change: {:?}", diff);
+
+            // More staff needed on Saturday evening
+            scheduler.apply(Change::Demand { day: Days::Saturday, shift: Shifts::evening(), demand: StaffingDemand { min: 3, max: None } });
+            let (schedule, diff) = scheduler.repair(&previous).expect("Schedule should be repaired");
+            assert!(schedule.assignments(Days::Saturday, &Shifts::evening()).len() >= 3);
+            assert!(diff.added.iter().any(|(day, shift, _)| *day == Days::Saturday && shift == &Shifts::evening()));
+
+            // Emp 1 moves to fewer hours than they were scheduled, once the other changes are undone
+            scheduler.apply(Change::UpdateEmployee(employees[0].clone()));
+            scheduler.apply(Change::Demand { day: Days::Saturday, shift: Shifts::evening(), demand: StaffingDemand { min: 2, max: None } });
+            let worked = previous.days_for(&employees[1]);
+            let max_hours = (previous.minutes_for(&employees[1]) / 60) as usize - 8;
+            let emp = Employee { contract: Contract { min_hours: 0, max_hours: Some(max_hours) }, ..employees[1].clone() };
+            scheduler.apply(Change::UpdateEmployee(emp.clone()));
+            let (schedule, diff) = scheduler.repair(&previous).expect("Schedule should be repaired");
+            assert!(schedule.minutes_for(&emp) as usize <= max_hours * 60);
+            assert!(diff.removed.iter().any(|(_, _, e)| e.name == "Emp 1"));
+            assert!(!validate(&schedule, &scheduler.employees, &scheduler.config).iter()
+                .any(|v| matches!(v, Violation::ContractHours { .. })));
+            assert!(diff.added.iter().chain(&diff.removed).all(|(day, _, _)| worked.contains(day)),
+                "Only the days Emp 1 worked should change: {:?}", diff);
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let seed = test_seed();
+        let employees = create_employees(12, seed);
+        let mut history = Schedule::new();
+        history.insert(Days::Sunday, Shifts::evening(), employees[..6].to_vec());
+
+        let config = ScheduleConfig {
+            rest_rules: vec![RestRule::no_morning_after_evening()],
+            history: Some(history),
+            ..Default::default()
+        };
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+
+            assert_eq!(schedule.rest_violations(&config), vec![]);
+            for emp in schedule.assignments(Days::Monday, &Shifts::morning()) {
+                assert!(!employees[..6].contains(emp), "{} opens after closing", emp.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fair_weekends() {
+        let config = ScheduleConfig {
+            shifts: vec![Shifts::morning()],
+            shift_min_emps: 1,
+            shift_max_emps: Some(1),
+            undesirable: vec![Undesirable::weekends()],
+            fairness_weeks: 4,
+            ..Default::default()
+        };
+        let start = Date::parse("2026-11-02").unwrap();
+        let end = start.add_days(20);
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            // Without preferences, so only fairness decides who works the weekends
+            let employees: Vec<_> = (0..6).map(|i| employee(&format!("Emp {}", i))).collect();
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let roster = scheduler.get_roster(start, end).expect("Roster should be created");
+
+            // Six weekend shifts over three weeks, one for each employee
+            for emp in &employees {
+                let weekends = roster.shifts_for(emp).iter()
+                    .filter(|(date, _)| matches!(date.weekday(), Days::Saturday | Days::Sunday))
+                    .count();
+                assert_eq!(weekends, 1, "{} works {} weekend shifts", emp.name, weekends);
+            }
+
+            let metrics = scheduler.roster_metrics(&roster, start, end);
+            assert_eq!(metrics.last().map(|(_, m)| m.undesirable[0].1), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_roster_continuity() {
+        let seed = test_seed();
+        let config = ScheduleConfig {
+            rest_rules: vec![RestRule::no_morning_after_evening()],
+            max_consecutive_days: Some(3),
+            ..Default::default()
+        };
+        let start = Date::parse("2026-11-02").unwrap();
+        let end = start.add_days(27);
+
+        for (solver, count) in [(Box::new(GreedySolver) as Box<dyn Solver>, 16), (Box::new(BacktrackingSolver), 12)] {
+            let employees = create_employees(count, seed);
+            let scheduler = Scheduler { employees: employees.clone(), config: config.clone(), solver };
+            let roster = scheduler.get_roster(start, end).expect("Roster should be created");
+            assert_eq!(roster.weeks().count(), 4);
+
+            for emp in &employees {
+                let shifts = roster.shifts_for(emp);
+
+                let mut run = 0;
+                for (i, (date, shift)) in shifts.iter().enumerate() {
+                    let previous = i.checked_sub(1).map(|p| &shifts[p]).filter(|(d, _)| d.add_days(1) == *date);
+                    run = if previous.is_some() { run + 1 } else { 1 };
+                    assert!(run <= 3, "{} works more than 3 days up to {}", emp.name, date);
+                    if let Some((_, previous)) = previous {
+                        assert!(config.rest_allows(previous, shift), "{} works {} after {} on {}", emp.name, shift, previous, date);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_tie_break() {
+        let seed = test_seed();
+        let employees = create_employees(12, seed);
+        let mut reversed = employees.clone();
+        reversed.reverse();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let mut scheduler = Scheduler {
+                employees: employees.clone(),
+                config: ScheduleConfig { tie_break: TieBreak::Random, seed: 42, ..Default::default() },
+                solver,
+            };
+            let schedule = scheduler.get_schedule().expect("Schedule should be created");
+            assert_eq!(scheduler.get_schedule(), Ok(schedule.clone()));
+            assert_eq!(scheduler.get_best_rotation().map(|(best, _)| best), scheduler.get_best_rotation().map(|(best, _)| best));
 
-        for (name, count) in emp_shifts {
-            assert!(count <= EMP_MAX_DAYS, "Employee {} worked too many days {}", name, count);
+            // Ordering by name ignores the order employees were added in
+            scheduler.config.tie_break = TieBreak::Name;
+            let by_name = scheduler.get_schedule().expect("Schedule should be created");
+            scheduler.employees = reversed.clone();
+            assert_eq!(scheduler.get_schedule(), Ok(by_name));
         }
     }
 }
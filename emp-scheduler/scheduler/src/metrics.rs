@@ -0,0 +1,240 @@
+use std::fmt;
+
+use crate::{Date, Days, Employee, Roster, Schedule, ScheduleConfig, Scheduler, Shifts, Undesirable};
+
+/// Workload of one employee in a schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmployeeMetrics {
+    pub name: String,
+
+    pub shifts: usize,
+
+    pub days: usize,
+
+    /// Shifts worked that are the employee's first choice.
+    pub preferred: usize,
+
+    /// Shifts worked on Saturday or Sunday.
+    pub weekends: usize,
+
+    /// Minutes worked, excluding breaks.
+    pub minutes: u32,
+
+    /// Shifts worked in each of the config's `undesirable` groups, including the
+    /// counted past weeks.
+    pub undesirable: Vec<usize>,
+}
+
+impl EmployeeMetrics {
+
+    /// Percentage of the employee's shifts on their first choice, `0` when they work none.
+    pub fn preferred_percent(&self) -> f32 {
+        percent(self.preferred, self.shifts)
+    }
+}
+
+/// Measures of how good a schedule is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// Workload of every employee, in roster order.
+    pub employees: Vec<EmployeeMetrics>,
+
+    /// Percentage of all assignments on the employee's first choice of shift.
+    pub preferred_percent: f32,
+
+    /// Average shifts worked per employee.
+    pub mean_shifts: f32,
+
+    /// Standard deviation of the shifts worked per employee.
+    pub shifts_std_dev: f32,
+
+    /// Difference between the most and fewest shifts worked by an employee.
+    pub shifts_spread: usize,
+
+    /// Each `undesirable` group with the difference between the most and fewest of its
+    /// shifts worked by an employee.
+    pub undesirable: Vec<(Undesirable, usize)>,
+
+    /// Shifts with fewer employees than their minimum, and how many they have.
+    pub understaffed: Vec<(Days, Shifts, usize)>,
+
+    /// Shifts with more employees than their maximum, and how many they have.
+    pub overstaffed: Vec<(Days, Shifts, usize)>,
+}
+
+impl fmt::Display for Metrics {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let groups: Vec<_> = self.undesirable.iter().map(|(group, _)| group.to_string()).collect();
+        write!(f, "{:<24} {:>6} {:>4} {:>9} {:>8} {:>6}", "Employee", "Shifts", "Days", "Preferred", "Weekends", "Hours")?;
+        for group in &groups {
+            write!(f, " {:>8}", group)?;
+        }
+        writeln!(f)?;
+
+        for e in &self.employees {
+            write!(f, "{:<24} {:>6} {:>4} {:>8.0}% {:>8} {:>6.1}",
+                e.name, e.shifts, e.days, e.preferred_percent(), e.weekends, e.minutes as f32 / 60.0)?;
+            for (group, worked) in groups.iter().zip(&e.undesirable) {
+                write!(f, " {:>width$}", worked, width = group.len().max(8))?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Assignments on a preferred shift: {:.0}%", self.preferred_percent)?;
+        writeln!(f, "Shifts per employee: {:.1} average, {:.2} standard deviation, {} between most and fewest",
+            self.mean_shifts, self.shifts_std_dev, self.shifts_spread)?;
+        for (group, spread) in &self.undesirable {
+            writeln!(f, "{} shifts: {} between most and fewest", group, spread)?;
+        }
+        for (day, shift, found) in &self.understaffed {
+            writeln!(f, "Understaffed: {} {} with {}", day, shift, found)?;
+        }
+        for (day, shift, found) in &self.overstaffed {
+            writeln!(f, "Overstaffed: {} {} with {}", day, shift, found)?;
+        }
+        Ok(())
+    }
+}
+
+impl Schedule {
+
+    /// Measures the workload of each employee and the staffing of each shift in `config`.
+    pub fn metrics(&self, employees: &[Employee], config: &ScheduleConfig) -> Metrics {
+        let workloads: Vec<_> = employees.iter()
+            .map(|e| {
+                let shifts = self.shifts_for(e);
+                EmployeeMetrics {
+                    name: e.name.clone(),
+                    shifts: shifts.len(),
+                    days: self.days_worked(e),
+                    preferred: shifts.iter().filter(|(_, shift)| e.preferred_shift() == Some(shift)).count(),
+                    weekends: shifts.iter().filter(|(day, _)| matches!(day, Days::Saturday | Days::Sunday)).count(),
+                    minutes: self.minutes_for(e),
+                    undesirable: config.undesirable.iter().map(|group| config.undesirable_worked(e, group, self)).collect(),
+                }
+            })
+            .collect();
+
+        let total: usize = workloads.iter().map(|e| e.shifts).sum();
+        let preferred: usize = workloads.iter().map(|e| e.preferred).sum();
+        let mean = if workloads.is_empty() { 0.0 } else { total as f32 / workloads.len() as f32 };
+        let variance = if workloads.is_empty() {
+            0.0
+        } else {
+            workloads.iter().map(|e| (e.shifts as f32 - mean).powi(2)).sum::<f32>() / workloads.len() as f32
+        };
+        let most = workloads.iter().map(|e| e.shifts).max().unwrap_or(0);
+        let fewest = workloads.iter().map(|e| e.shifts).min().unwrap_or(0);
+
+        let undesirable = config.undesirable.iter().enumerate()
+            .map(|(index, group)| {
+                let worked = || workloads.iter().map(|e| e.undesirable[index]);
+                (group.clone(), worked().max().unwrap_or(0) - worked().min().unwrap_or(0))
+            })
+            .collect();
+
+        let mut understaffed = Vec::new();
+        let mut overstaffed = Vec::new();
+        for &day in &config.operational_days {
+            for shift in &config.shifts {
+                let found = self.assignments(day, shift).len();
+                let demand = config.demand(day, shift);
+                if found < demand.min {
+                    understaffed.push((day, shift.clone(), found));
+                } else if !demand.allows(found) {
+                    overstaffed.push((day, shift.clone(), found));
+                }
+            }
+        }
+
+        Metrics {
+            employees: workloads,
+            preferred_percent: percent(preferred, total),
+            mean_shifts: mean,
+            shifts_std_dev: variance.sqrt(),
+            shifts_spread: most - fewest,
+            undesirable,
+            understaffed,
+            overstaffed,
+        }
+    }
+}
+
+impl Scheduler {
+
+    /// Measures every week of a roster for the dates from `start` to `end`, with the
+    /// staffing and past weeks `get_roster` schedules them with, returning each with the
+    /// Monday of its week.
+    pub fn roster_metrics(&self, roster: &Roster, start: Date, end: Date) -> Vec<(Date, Metrics)> {
+        let empty = Schedule::new();
+        let mut metrics = Vec::new();
+        let mut past_weeks = self.config.past_weeks.clone();
+
+        let mut week = start.week_start();
+        while week <= end {
+            let config = ScheduleConfig { past_weeks: past_weeks.clone(), ..self.week_config(week, start, end, None) };
+            let schedule = roster.week_of(week).unwrap_or(&empty);
+            metrics.push((week, schedule.metrics(&self.employees, &config)));
+            self.count_past_week(&mut past_weeks, schedule);
+            week = week.add_days(7);
+        }
+        metrics
+    }
+}
+
+fn percent(part: usize, whole: usize) -> f32 {
+    if whole == 0 { 0.0 } else { part as f32 * 100.0 / whole as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StaffingDemand;
+    use crate::tests::employee;
+
+    #[test]
+    fn test_metrics() {
+        let (morning, evening) = (Shifts::morning(), Shifts::evening());
+        let employees = vec![
+            Employee { preferences: vec![morning.clone()], ..employee("Alice") },
+            Employee { preferences: vec![evening.clone()], ..employee("Bob") },
+            employee("Carol"),
+        ];
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, morning.clone(), vec![employees[0].clone(), employees[1].clone()]);
+        schedule.insert(Days::Saturday, morning.clone(), vec![employees[0].clone()]);
+        schedule.insert(Days::Saturday, evening.clone(), vec![employees[1].clone()]);
+        schedule.insert(Days::Sunday, morning.clone(), vec![employees[0].clone()]);
+
+        let config = ScheduleConfig {
+            operational_days: vec![Days::Monday, Days::Saturday, Days::Sunday],
+            shifts: vec![morning.clone(), evening.clone()],
+            shift_min_emps: 1,
+            shift_max_emps: Some(1),
+            undesirable: vec![Undesirable::weekends(), Undesirable::evenings()],
+            ..Default::default()
+        };
+        let metrics = schedule.metrics(&employees, &config);
+
+        assert_eq!(metrics.employees[0], EmployeeMetrics {
+            name: "Alice".into(),
+            shifts: 3,
+            days: 3,
+            preferred: 3,
+            weekends: 2,
+            minutes: 3 * morning.duration_minutes(),
+            undesirable: vec![2, 0],
+        });
+        assert_eq!(metrics.employees[1].preferred_percent(), 50.0);
+        assert_eq!(metrics.employees[2].shifts, 0);
+        assert_eq!(metrics.preferred_percent, 80.0);
+        assert_eq!((metrics.mean_shifts, metrics.shifts_spread), (5.0 / 3.0, 3));
+        assert!((metrics.shifts_std_dev - 1.247).abs() < 0.001);
+        assert_eq!(metrics.undesirable, vec![(Undesirable::weekends(), 2), (Undesirable::evenings(), 1)]);
+        assert_eq!(metrics.understaffed, vec![(Days::Monday, evening.clone(), 0), (Days::Sunday, evening, 0)]);
+        assert_eq!(metrics.overstaffed, vec![(Days::Monday, morning, 2)]);
+
+        let config = ScheduleConfig { demand: [((Days::Monday, Shifts::morning()), StaffingDemand { min: 2, max: None })].into(), ..config };
+        assert!(schedule.metrics(&employees, &config).overstaffed.is_empty());
+    }
+}
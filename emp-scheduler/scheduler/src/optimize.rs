@@ -0,0 +1,273 @@
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::{validate, Days, Employee, Schedule, Scheduler, SchedulerError, Score, Shifts};
+
+/// How long `Scheduler::improve` searches for a better schedule.
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+pub enum Budget {
+    /// Number of changed schedules to try.
+    Iterations(usize),
+
+    /// Time to search for.
+    Time(Duration),
+}
+
+/// A schedule after improving it, with its score before and after.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Improvement {
+    pub schedule: Schedule,
+
+    pub before: Score,
+
+    pub after: Score,
+
+    /// Number of changed schedules tried.
+    pub iterations: usize,
+}
+
+/// A change to a schedule tried while improving it.
+enum Move {
+    /// Gives the employee at a position of a shift's list to another employee.
+    Replace {
+        day: Days,
+        shift: Shifts,
+        index: usize,
+        employee: Employee,
+    },
+
+    /// Exchanges the employees at positions of two shifts' lists.
+    Swap {
+        first: (Days, Shifts, usize),
+        second: (Days, Shifts, usize),
+    },
+}
+
+impl Scheduler {
+
+    /// Improves the score of `schedule` by giving shifts to other employees and swapping
+    /// employees between shifts, keeping only changes that break none of the rules the
+    /// schedule kept.
+    ///
+    /// The first change lowering the score is taken each time, until none does or the
+    /// budget runs out.
+    pub fn improve(&self, schedule: &Schedule, budget: Budget) -> Improvement {
+        let score = |schedule: &Schedule| schedule.score(&self.employees, &self.config);
+        let allowed = validate(schedule, &self.employees, &self.config);
+        let started = Instant::now();
+        let exhausted = |iterations| match budget {
+            Budget::Iterations(max) => iterations >= max,
+            Budget::Time(time) => started.elapsed() >= time,
+        };
+
+        let before = score(schedule);
+        let mut current = (schedule.clone(), before);
+        let mut iterations = 0;
+
+        'search: loop {
+            let mut improved = false;
+            for change in self.moves(&current.0) {
+                if exhausted(iterations) {
+                    break 'search;
+                }
+                iterations += 1;
+
+                let candidate = apply(&current.0, change);
+                let candidate_score = score(&candidate);
+                if candidate_score.total < current.1.total
+                    && validate(&candidate, &self.employees, &self.config).iter().all(|v| allowed.contains(v)) {
+                    current = (candidate, candidate_score);
+                    improved = true;
+                    break;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        let (schedule, after) = current;
+        Improvement { schedule, before, after, iterations }
+    }
+
+    /// Returns the lowest scoring schedule found for the week with its score, improving
+    /// the best rotation of the employees within the budget.
+    pub fn get_best_schedule(&self, budget: Budget) -> Result<(Schedule, Score), SchedulerError> {
+        let (schedule, _) = self.get_best_rotation()?;
+        let Improvement { schedule, after, .. } = self.improve(&schedule, budget);
+        Ok((schedule, after))
+    }
+
+    /// Every replacement and swap of employees in the schedule.
+    fn moves(&self, schedule: &Schedule) -> Vec<Move> {
+        let slots: Vec<_> = schedule.iter().collect();
+        let mut moves = Vec::new();
+
+        for &(day, shift, assigned) in &slots {
+            for index in 0..assigned.len() {
+                for employee in self.employees.iter().filter(|e| !assigned.iter().any(|a| a.name == e.name)) {
+                    moves.push(Move::Replace { day, shift: shift.clone(), index, employee: employee.clone() });
+                }
+            }
+        }
+
+        for (i, &(day, shift, assigned)) in slots.iter().enumerate() {
+            for &(other_day, other_shift, other_assigned) in &slots[i + 1..] {
+                for (index, employee) in assigned.iter().enumerate() {
+                    for (other_index, other) in other_assigned.iter().enumerate() {
+                        // Only swap employees into shifts they are not already on
+                        if assigned.iter().any(|e| e.name == other.name) || other_assigned.iter().any(|e| e.name == employee.name) {
+                            continue;
+                        }
+                        moves.push(Move::Swap {
+                            first: (day, shift.clone(), index),
+                            second: (other_day, other_shift.clone(), other_index),
+                        });
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+/// A copy of the schedule with the change made.
+fn apply(schedule: &Schedule, change: Move) -> Schedule {
+    let mut changed = schedule.clone();
+    match change {
+        Move::Replace { day, shift, index, employee } => {
+            let mut assigned = schedule.assignments(day, &shift).to_vec();
+            assigned[index] = employee;
+            changed.insert(day, shift, assigned);
+        },
+        Move::Swap { first: (day, shift, index), second: (other_day, other_shift, other_index) } => {
+            let mut assigned = schedule.assignments(day, &shift).to_vec();
+            let mut other_assigned = schedule.assignments(other_day, &other_shift).to_vec();
+            mem::swap(&mut assigned[index], &mut other_assigned[other_index]);
+            changed.insert(day, shift, assigned);
+            changed.insert(other_day, other_shift, other_assigned);
+        },
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BacktrackingSolver, GreedySolver, ScheduleConfig, Solver, TimeOff};
+    use crate::tests::employee;
+
+    #[test]
+    fn test_improve() {
+        let (morning, evening) = (Shifts::morning(), Shifts::evening());
+        let alice = Employee { preferences: vec![evening.clone()], ..employee("Alice") };
+        let bob = Employee { preferences: vec![morning.clone()], ..employee("Bob") };
+        let scheduler = Scheduler {
+            employees: vec![alice.clone(), bob.clone()],
+            config: ScheduleConfig {
+                operational_days: vec![Days::Monday],
+                shifts: vec![morning.clone(), evening.clone()],
+                shift_min_emps: 1,
+                shift_max_emps: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, morning.clone(), vec![alice.clone()]);
+        schedule.insert(Days::Monday, evening.clone(), vec![bob.clone()]);
+
+        let improvement = scheduler.improve(&schedule, Budget::Iterations(100));
+        assert_eq!((improvement.before.preference, improvement.after.preference), (2, 0));
+        assert_eq!(improvement.schedule.assignments(Days::Monday, &morning), &[bob]);
+        assert_eq!(improvement.schedule.assignments(Days::Monday, &evening), &[alice]);
+
+        // Nothing is tried without a budget
+        let improvement = scheduler.improve(&schedule, Budget::Iterations(0));
+        assert_eq!((improvement.schedule, improvement.after, improvement.iterations), (schedule, improvement.before, 0));
+    }
+
+    #[test]
+    fn test_improve_breaks_no_new_rules() {
+        let morning = Shifts::morning();
+        let alice = Employee {
+            preferences: vec![morning.clone()],
+            time_off: vec![TimeOff { day: Days::Monday, shift: None }],
+            ..employee("Alice")
+        };
+        let bob = Employee { preferences: vec![Shifts::evening()], unavailable: vec![morning.clone()], ..employee("Bob") };
+        let carol = employee("Carol");
+        let scheduler = Scheduler {
+            employees: vec![alice, bob.clone(), carol.clone()],
+            config: ScheduleConfig {
+                operational_days: vec![Days::Monday],
+                shifts: vec![morning.clone()],
+                shift_min_emps: 1,
+                shift_max_emps: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Alice would fix Bob being unavailable by working on her time off, Carol breaks no rule
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, morning.clone(), vec![bob]);
+        let improvement = scheduler.improve(&schedule, Budget::Iterations(100));
+        assert_eq!(improvement.schedule.assignments(Days::Monday, &morning), &[carol]);
+        assert_eq!(validate(&improvement.schedule, &scheduler.employees, &scheduler.config), vec![]);
+    }
+
+    #[test]
+    fn test_improve_keeps_rules() {
+        let shifts = Shifts::standard();
+        let employees: Vec<_> = (0..9)
+            .map(|i| Employee { preferences: vec![shifts[i % 3].clone()], ..employee(&format!("Emp {}", i)) })
+            .collect();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler {
+                employees: employees.clone(),
+                config: ScheduleConfig { shift_min_emps: 1, min_rest_hours: Some(12), ..Default::default() },
+                solver,
+            };
+            let schedule = scheduler.get_schedule().unwrap();
+            let improvement = scheduler.improve(&schedule, Budget::Time(Duration::from_secs(5)));
+
+            assert!(improvement.after.total <= improvement.before.total);
+            assert_eq!(improvement.after, improvement.schedule.score(&scheduler.employees, &scheduler.config));
+            assert_eq!(validate(&improvement.schedule, &scheduler.employees, &scheduler.config), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_get_best_schedule() {
+        let (morning, evening) = (Shifts::morning(), Shifts::evening());
+        // Alice takes the morning first, leaving Bob the unranked evening
+        let alice = Employee { preferences: vec![morning.clone(), evening.clone()], ..employee("Alice") };
+        let bob = Employee { preferences: vec![morning.clone(), Shifts::afternoon()], ..employee("Bob") };
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler {
+                employees: vec![alice.clone(), bob.clone()],
+                config: ScheduleConfig {
+                    operational_days: vec![Days::Monday],
+                    shifts: vec![morning.clone(), evening.clone()],
+                    shift_min_emps: 1,
+                    shift_max_emps: Some(1),
+                    ..Default::default()
+                },
+                solver,
+            };
+            let schedule = scheduler.get_schedule().unwrap();
+            let (best, score) = scheduler.get_best_schedule(Budget::Iterations(100)).unwrap();
+
+            assert!(score.total < schedule.score(&scheduler.employees, &scheduler.config).total);
+            assert_eq!(score, best.score(&scheduler.employees, &scheduler.config));
+            assert_eq!(best.shifts_for(&bob), [(Days::Monday, morning.clone())]);
+            assert_eq!(best.shifts_for(&alice), [(Days::Monday, evening.clone())]);
+            assert_eq!(validate(&best, &scheduler.employees, &scheduler.config), vec![]);
+        }
+    }
+}
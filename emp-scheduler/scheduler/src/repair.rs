@@ -0,0 +1,142 @@
+use crate::{Assignment, Days, Employee, Schedule, ScheduleConfig, ScheduleDiff, Scheduler, SchedulerError, Shifts, StaffingDemand};
+
+/// A change to the employees or staffing after a schedule was published.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The employee with this name leaves the schedule.
+    RemoveEmployee(String),
+
+    /// Replaces the employee with the same name, e.g. with new time off or availability,
+    /// or adds them when they are not on the roster.
+    UpdateEmployee(Employee),
+
+    /// Sets the staffing for a shift.
+    Demand {
+        day: Days,
+        shift: Shifts,
+        demand: StaffingDemand,
+    },
+}
+
+impl Scheduler {
+
+    pub fn apply(&mut self, change: Change) {
+        match change {
+            Change::RemoveEmployee(name) => self.employees.retain(|e| e.name != name),
+            Change::UpdateEmployee(employee) => {
+                match self.employees.iter_mut().find(|e| e.name == employee.name) {
+                    Some(existing) => *existing = employee,
+                    None => self.employees.push(employee),
+                }
+            },
+            Change::Demand { day, shift, demand } => {
+                self.config.demand.insert((day, shift), demand);
+            },
+        }
+    }
+
+    /// Rebuilds `previous` for the current employees and rules, changing as few
+    /// assignments as possible, and returns it with the assignments that changed.
+    ///
+    /// Assignments that still fit are pinned and only the days that changed are
+    /// solved again. When that fails, the days around them are opened up one at a
+    /// time before falling back to solving the whole week.
+    pub fn repair(&self, previous: &Schedule) -> Result<(Schedule, ScheduleDiff), SchedulerError> {
+        let (kept, changed_days) = self.keepable(previous);
+
+        let distance = |a: Days, b: Days| {
+            let index = |day| Days::iter().position(|d| d == day).unwrap_or(0) as i32;
+            (index(a) - index(b)).unsigned_abs() as usize
+        };
+        let attempts = (0..Days::iter().count())
+            .map(|radius| {
+                kept.iter()
+                    .filter(|pin| changed_days.iter().all(|&day| distance(pin.day, day) > radius))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .chain([Vec::new()]);
+
+        let mut result = Err(SchedulerError::Infeasible);
+        for pinned in attempts {
+            let config = ScheduleConfig {
+                pinned: self.config.pinned.iter().cloned().chain(pinned).collect(),
+                ..self.config.clone()
+            };
+            result = self.schedule_week(&config);
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        let mut schedule = result?;
+
+        // List employees who kept a shift in the order they were listed before
+        let slots: Vec<_> = schedule.iter().map(|(day, shift, employees)| (day, shift.clone(), employees.to_vec())).collect();
+        for (day, shift, mut employees) in slots {
+            let before = previous.assignments(day, &shift);
+            employees.sort_by_key(|e| before.iter().position(|b| b.name == e.name).unwrap_or(usize::MAX));
+            schedule.insert(day, shift, employees);
+        }
+
+        let diff = previous.diff(&schedule);
+        Ok((schedule, diff))
+    }
+
+    /// Assignments of `previous` the current employees can still work within their
+    /// hours and the rest and working days rules, and the days where assignments were
+    /// lost or more employees are needed.
+    fn keepable(&self, previous: &Schedule) -> (Vec<Assignment>, Vec<Days>) {
+        let config = &self.config;
+        let mut kept = Vec::new();
+        let mut changed_days = Vec::new();
+
+        for &day in &config.operational_days {
+            for shift in &config.shifts {
+                let demand = config.demand(day, shift);
+                let mut count = 0;
+                for old in previous.assignments(day, shift) {
+                    match self.employees.iter().find(|e| e.name == old.name) {
+                        Some(employee) if config.can_assign(employee, day, shift) && demand.allows(count + 1)
+                            && self.still_fits(employee, day, shift, &kept) => {
+                            kept.push(Assignment::new(&employee.name, day, shift.clone()));
+                            count += 1;
+                        },
+                        _ => changed_days.push(day),
+                    }
+                }
+                if count < demand.min {
+                    changed_days.push(day);
+                }
+            }
+        }
+
+        // Shifts that are no longer worked
+        for (day, shift, employees) in previous.iter() {
+            if !employees.is_empty() && (!config.operational_days.contains(&day) || !config.shifts.contains(shift)) {
+                changed_days.push(day);
+            }
+        }
+
+        changed_days.sort_by_key(|&day| Days::iter().position(|d| d == day));
+        changed_days.dedup();
+        (kept, changed_days)
+    }
+
+    /// Checks an employee can keep a shift alongside the shifts already `kept` for them.
+    fn still_fits(&self, employee: &Employee, day: Days, shift: &Shifts, kept: &[Assignment]) -> bool {
+        let config = &self.config;
+        let theirs: Vec<_> = kept.iter().filter(|a| a.employee == employee.name).collect();
+        let shift_on = |day: Option<Days>| day.and_then(|day| theirs.iter().find(|a| a.day == day)).map(|a| &a.shift);
+
+        let previous = shift_on(config.previous_day(day))
+            .or(if day == Days::Monday { config.carried_shift(employee) } else { None });
+        let days: Vec<_> = theirs.iter().map(|a| a.day).chain([day]).collect();
+        let minutes = theirs.iter().map(|a| a.shift.duration_minutes()).sum::<u32>() + shift.duration_minutes();
+
+        previous.is_none_or(|previous| config.rest_allows(previous, shift))
+            && shift_on(config.next_day(day)).is_none_or(|next| config.rest_allows(shift, next))
+            && config.allows_work_days_for(employee, &days)
+            && employee.contract.allows(minutes)
+    }
+}
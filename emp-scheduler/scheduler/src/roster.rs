@@ -0,0 +1,98 @@
+use crate::{Date, Employee, Schedule, Shifts};
+
+/// Employees assigned to shifts on calendar dates, one `Schedule` per week.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Roster {
+    weeks: Vec<(Date, Schedule)>,
+}
+
+impl Roster {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the schedule for the week starting on the Monday of `week`.
+    pub fn insert_week(&mut self, week: Date, schedule: Schedule) {
+        let week = week.week_start();
+        match self.weeks.binary_search_by_key(&week, |&(start, _)| start) {
+            Ok(index) => self.weeks[index].1 = schedule,
+            Err(index) => self.weeks.insert(index, (week, schedule)),
+        }
+    }
+
+    /// Scheduled weeks in date order, with the Monday starting each week.
+    pub fn weeks(&self) -> impl Iterator<Item = (Date, &Schedule)> {
+        self.weeks.iter().map(|(start, schedule)| (*start, schedule))
+    }
+
+    /// Schedule for the week containing `date`.
+    pub fn week_of(&self, date: Date) -> Option<&Schedule> {
+        self.weeks.iter().find(|&&(start, _)| start == date.week_start()).map(|(_, schedule)| schedule)
+    }
+
+    /// Employees working a shift on a date, empty if the shift is not scheduled.
+    pub fn assignments(&self, date: Date, shift: &Shifts) -> &[Employee] {
+        self.week_of(date).map_or(&[], |schedule| schedule.assignments(date.weekday(), shift))
+    }
+
+    /// Scheduled shifts in date order, then by shift start time.
+    pub fn iter(&self) -> impl Iterator<Item = (Date, &Shifts, &[Employee])> {
+        self.weeks().flat_map(|(start, schedule)| {
+            schedule.iter().map(move |(day, shift, employees)| (start.in_week(day), shift, employees))
+        })
+    }
+
+    /// Dates with at least one scheduled shift.
+    pub fn dates(&self) -> Vec<Date> {
+        let mut dates: Vec<_> = self.iter().map(|(date, _, _)| date).collect();
+        dates.dedup();
+        dates
+    }
+
+    /// Shifts worked by an employee in date order.
+    pub fn shifts_for(&self, employee: &Employee) -> Vec<(Date, Shifts)> {
+        self.iter()
+            .filter(|(_, _, employees)| employees.contains(employee))
+            .map(|(date, shift, _)| (date, shift.clone()))
+            .collect()
+    }
+
+    /// Hours an employee works over the whole roster, excluding breaks.
+    pub fn hours_for(&self, employee: &Employee) -> f32 {
+        self.weeks().map(|(_, schedule)| schedule.hours_for(employee)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weeks.iter().all(|(_, schedule)| schedule.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Days;
+    use crate::tests::employee;
+
+    #[test]
+    fn test_dated_assignments() {
+        let alice = employee("Alice");
+
+        let mut first = Schedule::new();
+        first.insert(Days::Sunday, Shifts::evening(), vec![alice.clone()]);
+        let mut second = Schedule::new();
+        second.insert(Days::Monday, Shifts::morning(), vec![alice.clone()]);
+
+        let mut roster = Roster::new();
+        roster.insert_week(Date::parse("2026-11-11").unwrap(), second);
+        roster.insert_week(Date::parse("2026-11-02").unwrap(), first);
+
+        let sunday = Date::parse("2026-11-08").unwrap();
+        let monday = Date::parse("2026-11-09").unwrap();
+        assert_eq!(roster.assignments(sunday, &Shifts::evening()), vec![alice.clone()]);
+        assert!(roster.assignments(monday, &Shifts::evening()).is_empty());
+        assert_eq!(roster.dates(), vec![sunday, monday]);
+        assert_eq!(roster.shifts_for(&alice), vec![(sunday, Shifts::evening()), (monday, Shifts::morning())]);
+        assert_eq!(roster.hours_for(&alice), 16.0);
+    }
+}
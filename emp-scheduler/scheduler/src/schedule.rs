@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Days, Employee, ScheduleConfig, Shifts};
+
+/// An employee working shifts on consecutive days without the required rest.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RestViolation {
+    pub employee: String,
+    pub day: Days,
+    pub shift: Shifts,
+    pub next_shift: Shifts,
+}
+
+/// Assignments added and removed between two schedules, matching employees by name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScheduleDiff {
+    /// Shifts employees work that they did not before.
+    pub added: Vec<(Days, Shifts, Employee)>,
+
+    /// Shifts employees no longer work.
+    pub removed: Vec<(Days, Shifts, Employee)>,
+}
+
+impl ScheduleDiff {
+
+    /// Number of changed assignments.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Names of the employees whose shifts changed, in name order.
+    pub fn affected_employees(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.added.iter().chain(&self.removed).map(|(_, _, e)| e.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// Employees assigned to every shift of the week.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schedule {
+    shifts: HashMap<Days, BTreeMap<Shifts, Vec<Employee>>>,
+}
+
+impl Schedule {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the employees working a shift, replacing any previous assignment.
+    pub fn insert(&mut self, day: Days, shift: Shifts, employees: Vec<Employee>) {
+        self.shifts.entry(day).or_default().insert(shift, employees);
+    }
+
+    /// Employees working a shift, empty if the shift is not scheduled.
+    pub fn assignments(&self, day: Days, shift: &Shifts) -> &[Employee] {
+        self.shifts.get(&day)
+            .and_then(|day_shifts| day_shifts.get(shift))
+            .map(|employees| employees.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Scheduled shifts in `Days` order, then by shift start time.
+    pub fn iter(&self) -> impl Iterator<Item = (Days, &Shifts, &[Employee])> {
+        Days::iter().flat_map(move |day| {
+            self.shifts.get(&day).into_iter().flatten()
+                .map(move |(shift, employees)| (day, shift, employees.as_slice()))
+        })
+    }
+
+    /// Shifts worked by an employee in `Days` order.
+    pub fn shifts_for(&self, employee: &Employee) -> Vec<(Days, Shifts)> {
+        self.iter()
+            .filter(|(_, _, employees)| employees.contains(employee))
+            .map(|(day, shift, _)| (day, shift.clone()))
+            .collect()
+    }
+
+    /// Minutes an employee works in the week, excluding breaks.
+    pub fn minutes_for(&self, employee: &Employee) -> u32 {
+        self.shifts_for(employee).iter().map(|(_, shift)| shift.duration_minutes()).sum()
+    }
+
+    /// Hours an employee works in the week, excluding breaks.
+    pub fn hours_for(&self, employee: &Employee) -> f32 {
+        self.minutes_for(employee) as f32 / 60.0
+    }
+
+    /// Number of distinct days an employee works.
+    pub fn days_worked(&self, employee: &Employee) -> usize {
+        self.days_for(employee).len()
+    }
+
+    /// Days an employee works in `Days` order.
+    pub fn days_for(&self, employee: &Employee) -> Vec<Days> {
+        Days::iter().filter(|&day| self.employees_on(day).contains(&employee)).collect()
+    }
+
+    /// Employees working any shift on a day, in shift start time order.
+    pub fn employees_on(&self, day: Days) -> Vec<&Employee> {
+        self.shifts.get(&day).into_iter().flat_map(|day_shifts| day_shifts.values().flatten()).collect()
+    }
+
+    /// Finds employees without the rest required by `config` between two days, matching
+    /// employees by name.
+    pub fn rest_violations(&self, config: &ScheduleConfig) -> Vec<RestViolation> {
+        let mut violations = Vec::new();
+
+        for (day, shift, employees) in self.iter() {
+            let Some(next_day) = config.next_day(day) else {
+                continue;
+            };
+
+            for next_shift in config.shifts.iter().filter(|next| !config.rest_allows(shift, next)) {
+                let next_employees = self.assignments(next_day, next_shift);
+                for emp in employees.iter().filter(|e| next_employees.iter().any(|n| n.name == e.name)) {
+                    violations.push(RestViolation {
+                        employee: emp.name.clone(),
+                        day,
+                        shift: shift.clone(),
+                        next_shift: next_shift.clone(),
+                    });
+                }
+            }
+        }
+
+        // From the Sunday of the history into Monday
+        let history = config.history.iter()
+            .flat_map(|history| history.iter())
+            .filter(|&(day, _, _)| day == Days::Sunday && config.previous_day(Days::Monday).is_none());
+        for (day, shift, employees) in history {
+            for next_shift in config.shifts.iter().filter(|next| !config.rest_allows(shift, next)) {
+                let next_employees = self.assignments(Days::Monday, next_shift);
+                for emp in employees.iter().filter(|e| next_employees.iter().any(|n| n.name == e.name)) {
+                    violations.push(RestViolation {
+                        employee: emp.name.clone(),
+                        day,
+                        shift: shift.clone(),
+                        next_shift: next_shift.clone(),
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Assignments that differ in `other`, matching employees by name.
+    pub fn diff(&self, other: &Schedule) -> ScheduleDiff {
+        let missing = |from: &Schedule, to: &Schedule| {
+            let mut changes = Vec::new();
+            for (day, shift, employees) in from.iter() {
+                let kept = to.assignments(day, shift);
+                for emp in employees.iter().filter(|e| !kept.iter().any(|k| k.name == e.name)) {
+                    changes.push((day, shift.clone(), emp.clone()));
+                }
+            }
+            changes
+        };
+
+        ScheduleDiff { added: missing(other, self), removed: missing(self, other) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shifts.values().all(|day_shifts| day_shifts.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RestRule;
+    use crate::tests::employee;
+
+    #[test]
+    fn test_queries() {
+        let (alice, bob) = (employee("Alice"), employee("Bob"));
+
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Tuesday, Shifts::evening(), vec![alice.clone()]);
+        schedule.insert(Days::Monday, Shifts::morning(), vec![alice.clone(), bob.clone()]);
+        schedule.insert(Days::Monday, Shifts::afternoon(), vec![]);
+
+        assert_eq!(schedule.assignments(Days::Monday, &Shifts::morning()), &[alice.clone(), bob.clone()]);
+        assert!(schedule.assignments(Days::Sunday, &Shifts::morning()).is_empty());
+
+        assert_eq!(schedule.shifts_for(&alice), vec![(Days::Monday, Shifts::morning()), (Days::Tuesday, Shifts::evening())]);
+        assert_eq!(schedule.days_worked(&alice), 2);
+        assert_eq!(schedule.days_worked(&bob), 1);
+        assert_eq!(schedule.employees_on(Days::Monday), vec![&alice, &bob]);
+        assert_eq!(schedule.hours_for(&alice), 16.0);
+
+        let order: Vec<_> = schedule.iter().map(|(day, shift, _)| (day, shift.clone())).collect();
+        assert_eq!(order, vec![
+            (Days::Monday, Shifts::morning()),
+            (Days::Monday, Shifts::afternoon()),
+            (Days::Tuesday, Shifts::evening()),
+        ]);
+    }
+
+    #[test]
+    fn test_rest_violations() {
+        let (alice, bob) = (employee("Alice"), employee("Bob"));
+
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, Shifts::evening(), vec![alice.clone(), bob.clone()]);
+        schedule.insert(Days::Tuesday, Shifts::morning(), vec![alice.clone()]);
+        schedule.insert(Days::Tuesday, Shifts::afternoon(), vec![bob.clone()]);
+        schedule.insert(Days::Sunday, Shifts::evening(), vec![bob.clone()]);
+        schedule.insert(Days::Monday, Shifts::morning(), vec![bob.clone()]);
+
+        assert!(schedule.rest_violations(&ScheduleConfig::default()).is_empty());
+
+        let config = ScheduleConfig { rest_rules: vec![RestRule::no_morning_after_evening()], ..Default::default() };
+        assert_eq!(schedule.rest_violations(&config), vec![RestViolation {
+            employee: "Alice".into(),
+            day: Days::Monday,
+            shift: Shifts::evening(),
+            next_shift: Shifts::morning(),
+        }]);
+    }
+
+    #[test]
+    fn test_diff() {
+        let (alice, bob, carol) = (employee("Alice"), employee("Bob"), employee("Carol"));
+
+        let mut before = Schedule::new();
+        before.insert(Days::Monday, Shifts::morning(), vec![alice.clone(), bob.clone()]);
+        before.insert(Days::Tuesday, Shifts::evening(), vec![bob.clone()]);
+
+        // Alice's availability changed, which still matches her by name
+        let alice = Employee { unavailable_days: vec![Days::Sunday], ..alice };
+        let mut after = Schedule::new();
+        after.insert(Days::Monday, Shifts::morning(), vec![alice.clone(), carol.clone()]);
+        after.insert(Days::Tuesday, Shifts::evening(), vec![bob.clone()]);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added, vec![(Days::Monday, Shifts::morning(), carol)]);
+        assert_eq!(diff.removed, vec![(Days::Monday, Shifts::morning(), bob)]);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff.affected_employees(), vec!["Bob", "Carol"]);
+        assert!(after.diff(&after).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::{Employee, Schedule, ScheduleConfig, Scheduler, SchedulerError};
+
+/// How much each goal counts towards the score of a schedule, `0` to ignore it.
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Weights {
+    /// Employees working the shifts they prefer.
+    pub preference: u32,
+
+    /// Employees working a similar number of days.
+    pub fairness: u32,
+
+    /// Employees working a similar number of shifts in each `undesirable` group, such
+    /// as weekends.
+    pub undesirable: u32,
+}
+
+impl Default for Weights {
+
+    fn default() -> Self {
+        Self { preference: 1, fairness: 1, undesirable: 1 }
+    }
+}
+
+impl fmt::Display for Weights {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "preference={},fairness={},undesirable={}", self.preference, self.fairness, self.undesirable)
+    }
+}
+
+/// Penalties of a schedule for each goal, lower being better.
+#[derive(Debug, Copy, Clone, Default, Eq, Hash, PartialEq)]
+pub struct Score {
+    /// Ranks of the shifts worked in each employee's preferences, `0` for a first choice.
+    pub preference: u32,
+
+    /// Days worked away from the average, summed over employees.
+    pub fairness: u32,
+
+    /// Shifts of each `undesirable` group worked away from the average, including the
+    /// counted past weeks, summed over employees and groups.
+    pub undesirable: u32,
+
+    /// Penalties multiplied by their weights and added up, saturating at `u32::MAX`.
+    pub total: u32,
+}
+
+impl fmt::Display for Score {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (preference {}, fairness {}, undesirable {})", self.total, self.preference, self.fairness, self.undesirable)
+    }
+}
+
+impl Schedule {
+
+    /// Scores the schedule against the goals weighted by `config.weights`.
+    pub fn score(&self, employees: &[Employee], config: &ScheduleConfig) -> Score {
+        let preference = self.iter()
+            .flat_map(|(_, shift, assigned)| assigned.iter().map(move |e| (e, shift)))
+            .map(|(e, shift)| e.preference_rank(shift).unwrap_or(e.preferences.len()) as u32)
+            .sum();
+
+        let days: Vec<_> = employees.iter().map(|e| self.days_worked(e)).collect();
+        let fairness = spread(&days);
+        let undesirable = config.undesirable.iter()
+            .map(|group| {
+                let worked: Vec<_> = employees.iter().map(|e| config.undesirable_worked(e, group, self)).collect();
+                spread(&worked)
+            })
+            .sum();
+
+        let weights = config.weights;
+        Score {
+            preference,
+            fairness,
+            undesirable,
+            total: weights.preference.saturating_mul(preference)
+                .saturating_add(weights.fairness.saturating_mul(fairness))
+                .saturating_add(weights.undesirable.saturating_mul(undesirable)),
+        }
+    }
+}
+
+impl Scheduler {
+
+    /// Solves the week once for every rotation of the tie-break order of the employees,
+    /// and returns the lowest scoring of those schedules.
+    ///
+    /// This is a heuristic running the solver once per employee: other schedules meeting
+    /// the rules may score lower, and `get_best_schedule` searches for them.
+    pub fn get_best_rotation(&self) -> Result<(Schedule, Score), SchedulerError> {
+        let schedule = self.schedule_week(&self.config)?;
+        let score = schedule.score(&self.employees, &self.config);
+        let mut best = (schedule, score);
+
+        // The solvers favour employees earlier in the list when breaking ties
+        let ordered = self.config.tie_break.order(&self.employees, self.config.seed);
+        for start in 1..ordered.len() {
+            let mut employees = ordered.clone();
+            employees.rotate_left(start);
+            if let Ok(schedule) = self.solver.solve(&employees, &self.config) {
+                let score = schedule.score(&self.employees, &self.config);
+                if score.total < best.1.total {
+                    best = (schedule, score);
+                }
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+/// Distance of each count from the average, summed and divided by the number of counts.
+fn spread(counts: &[usize]) -> u32 {
+    let n = counts.len();
+    let total: usize = counts.iter().sum();
+    if n == 0 {
+        return 0;
+    }
+    (counts.iter().map(|&count| (count * n).abs_diff(total)).sum::<usize>() / n) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate, BacktrackingSolver, Days, GreedySolver, Shifts, Solver, Undesirable};
+    use crate::tests::employee;
+
+    #[test]
+    fn test_score() {
+        let employees = vec![
+            Employee { preferences: vec![Shifts::morning(), Shifts::evening()], ..employee("Alice") },
+            Employee { preferences: vec![Shifts::evening()], ..employee("Bob") },
+            employee("Carol"),
+        ];
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, Shifts::morning(), vec![employees[0].clone(), employees[1].clone()]);
+        schedule.insert(Days::Saturday, Shifts::evening(), vec![employees[0].clone()]);
+        schedule.insert(Days::Sunday, Shifts::morning(), vec![employees[2].clone()]);
+
+        // Undesirable shifts are only shared out when groups are given
+        let config = ScheduleConfig::default();
+        assert_eq!(schedule.score(&employees, &config), Score { preference: 2, fairness: 1, undesirable: 0, total: 3 });
+
+        let config = ScheduleConfig { undesirable: vec![Undesirable::weekends(), Undesirable::evenings()], ..config };
+        assert_eq!(schedule.score(&employees, &config), Score { preference: 2, fairness: 1, undesirable: 2, total: 5 });
+
+        let config = ScheduleConfig { weights: Weights { preference: 3, fairness: 0, undesirable: 2 }, ..config };
+        assert_eq!(schedule.score(&employees, &config).total, 10);
+
+        // Large weights saturate instead of overflowing
+        let heavy = ScheduleConfig { weights: Weights { preference: u32::MAX, fairness: u32::MAX, undesirable: 1 }, ..config.clone() };
+        assert_eq!(schedule.score(&employees, &heavy).total, u32::MAX);
+
+        // Bob worked the weekend before, evening out the weekends
+        let mut past = Schedule::new();
+        past.insert(Days::Sunday, Shifts::morning(), vec![employees[1].clone()]);
+        let config = ScheduleConfig { past_weeks: vec![past.clone()], fairness_weeks: 1, ..config };
+        assert_eq!(config.undesirable_worked(&employees[1], &Undesirable::weekends(), &schedule), 1);
+        assert_eq!(schedule.score(&employees, &config).undesirable, 1);
+
+        // Weeks before the counted ones are left out
+        let config = ScheduleConfig { past_weeks: vec![past, Schedule::new()], ..config };
+        assert_eq!(schedule.score(&employees, &config).undesirable, 2);
+    }
+
+    #[test]
+    fn test_get_best_rotation() {
+        let shifts = Shifts::standard();
+        let employees: Vec<_> = (0..9)
+            .map(|i| Employee {
+                preferences: vec![shifts[i % 3].clone(), shifts[(i + 1) % 3].clone()],
+                ..employee(&format!("Emp {}", i))
+            })
+            .collect();
+
+        for solver in [Box::new(GreedySolver) as Box<dyn Solver>, Box::new(BacktrackingSolver)] {
+            let scheduler = Scheduler {
+                employees: employees.clone(),
+                config: ScheduleConfig { shift_min_emps: 1, ..Default::default() },
+                solver,
+            };
+            let schedule = scheduler.get_schedule().unwrap();
+            let (best, score) = scheduler.get_best_rotation().unwrap();
+
+            assert_eq!(score, best.score(&scheduler.employees, &scheduler.config));
+            assert!(score.total <= schedule.score(&scheduler.employees, &scheduler.config).total);
+            assert_eq!(validate(&best, &scheduler.employees, &scheduler.config), vec![]);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Time of day a shift starts or ends, in minutes after midnight.
+#[derive(Debug, Copy, Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ShiftTime {
+    minutes: u32,
+}
+
+impl ShiftTime {
+
+    /// Creates a time of day, `None` if the hour or minute is out of range.
+    pub fn new(hour: u32, minute: u32) -> Option<Self> {
+        (hour < 24 && minute < 60).then_some(Self { minutes: hour * 60 + minute })
+    }
+
+    /// Parses a time written as `HH:MM`, or just `HH`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (hour, minute) = input.trim().split_once(':').unwrap_or((input.trim(), "0"));
+        Self::new(hour.parse().ok()?, minute.parse().ok()?)
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.minutes / 60
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minutes % 60
+    }
+
+    /// Minutes after midnight.
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+}
+
+impl fmt::Display for ShiftTime {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())
+    }
+}
+
+/// A shift worked on operational days, with its own start and end time.
+///
+/// A shift ending at or before its start time runs past midnight into the next day.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Shifts {
+    pub name: String,
+    pub start: ShiftTime,
+    pub end: ShiftTime,
+
+    /// Unpaid break taken during the shift, in minutes.
+    pub break_minutes: u32,
+}
+
+impl Shifts {
+
+    pub fn new(name: &str, start: ShiftTime, end: ShiftTime) -> Self {
+        Self { name: name.into(), start, end, break_minutes: 0 }
+    }
+
+    pub fn with_break(self, break_minutes: u32) -> Self {
+        Self { break_minutes, ..self }
+    }
+
+    /// Parses a shift written as `Name=HH:MM-HH:MM`, optionally followed by `/break minutes`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (name, times) = input.split_once('=')?;
+        let (times, break_minutes) = match times.split_once('/') {
+            Some((times, minutes)) => (times, minutes.trim().parse().ok()?),
+            None => (times, 0),
+        };
+        let (start, end) = times.split_once('-')?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self::new(name, ShiftTime::parse(start)?, ShiftTime::parse(end)?).with_break(break_minutes))
+    }
+
+    pub fn morning() -> Self {
+        Self::new("Morning", ShiftTime { minutes: 7 * 60 }, ShiftTime { minutes: 15 * 60 })
+    }
+
+    pub fn afternoon() -> Self {
+        Self::new("Afternoon", ShiftTime { minutes: 11 * 60 }, ShiftTime { minutes: 19 * 60 })
+    }
+
+    pub fn evening() -> Self {
+        Self::new("Evening", ShiftTime { minutes: 15 * 60 }, ShiftTime { minutes: 23 * 60 })
+    }
+
+    /// The default Morning, Afternoon and Evening shifts.
+    pub fn standard() -> Vec<Shifts> {
+        vec![Self::morning(), Self::afternoon(), Self::evening()]
+    }
+
+    /// Checks the shift runs past midnight.
+    pub fn is_overnight(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Minutes from the start of the shift's day until it ends.
+    fn end_minutes(&self) -> u32 {
+        if self.is_overnight() {
+            self.end.minutes + MINUTES_PER_DAY
+        } else {
+            self.end.minutes
+        }
+    }
+
+    /// Minutes worked, excluding the break.
+    pub fn duration_minutes(&self) -> u32 {
+        (self.end_minutes() - self.start.minutes).saturating_sub(self.break_minutes)
+    }
+
+    /// Hours worked, excluding the break.
+    pub fn hours(&self) -> f32 {
+        self.duration_minutes() as f32 / 60.0
+    }
+
+    /// Minutes between the end of this shift and the start of `next` on the following day.
+    pub fn rest_before(&self, next: &Shifts) -> u32 {
+        (MINUTES_PER_DAY + next.start.minutes).saturating_sub(self.end_minutes())
+    }
+}
+
+impl Ord for Shifts {
+
+    /// Orders shifts by start time, then end time and name.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.start, self.end_minutes(), &self.name, self.break_minutes)
+            .cmp(&(other.start, other.end_minutes(), &other.name, other.break_minutes))
+    }
+}
+
+impl PartialOrd for Shifts {
+
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Shifts {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shift() {
+        let lunch = Shifts::parse("Lunch=10:00-14:00").unwrap();
+        assert_eq!(lunch.name, "Lunch");
+        assert_eq!(lunch.start.to_string(), "10:00");
+        assert_eq!(lunch.duration_minutes(), 4 * 60);
+
+        let night = Shifts::parse("Night = 22-6:30 / 30").unwrap();
+        assert!(night.is_overnight());
+        assert_eq!(night.end.to_string(), "06:30");
+        assert_eq!(night.duration_minutes(), 8 * 60);
+        assert_eq!(night.hours(), 8.0);
+
+        assert_eq!(Shifts::parse("Lunch"), None);
+        assert_eq!(Shifts::parse("=10:00-14:00"), None);
+        assert_eq!(Shifts::parse("Late=25:00-02:00"), None);
+    }
+
+    #[test]
+    fn test_rest_and_order() {
+        let night = Shifts::parse("Night=22:00-06:00").unwrap();
+        assert_eq!(Shifts::evening().rest_before(&Shifts::morning()), 8 * 60);
+        assert_eq!(Shifts::afternoon().rest_before(&Shifts::morning()), 12 * 60);
+        assert_eq!(night.rest_before(&Shifts::morning()), 60);
+        assert_eq!(night.rest_before(&Shifts::evening()), 9 * 60);
+
+        let mut shifts = vec![night.clone(), Shifts::evening(), Shifts::morning(), Shifts::afternoon()];
+        shifts.sort();
+        assert_eq!(shifts, vec![Shifts::morning(), Shifts::afternoon(), Shifts::evening(), night]);
+    }
+}
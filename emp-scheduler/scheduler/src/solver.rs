@@ -0,0 +1,44 @@
+use crate::{Employee, Schedule, ScheduleConfig, SchedulerError};
+
+mod backtracking;
+mod greedy;
+
+pub use backtracking::BacktrackingSolver;
+pub use greedy::GreedySolver;
+
+/// Strategy used by the `Scheduler` to assign employees to shifts.
+pub trait Solver {
+
+    /// Name used to select the solver, e.g. from the command line.
+    fn name(&self) -> &str;
+
+    /// Builds a schedule for the employees following the rules in `config`.
+    fn solve(&self, employees: &[Employee], config: &ScheduleConfig) -> Result<Schedule, SchedulerError>;
+}
+
+/// Names of the solvers provided by this crate.
+pub const SOLVER_NAMES: &[&str] = &["greedy", "backtracking"];
+
+/// Returns the built in solver with the given name, ignoring case.
+pub fn solver_by_name(name: &str) -> Option<Box<dyn Solver>> {
+    match name.to_lowercase().as_str() {
+        "greedy" => Some(Box::new(GreedySolver)),
+        "backtracking" => Some(Box::new(BacktrackingSolver)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solver_by_name() {
+        for &name in SOLVER_NAMES {
+            let solver = solver_by_name(name).expect("Solver should exist");
+            assert_eq!(solver.name(), name);
+        }
+        assert_eq!(solver_by_name("Greedy").map(|s| s.name().to_string()), Some("greedy".into()));
+        assert!(solver_by_name("annealing").is_none());
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{Days, Employee, Schedule, ScheduleConfig, SchedulerError, Shifts, SkillRequirement, Undesirable};
+
+use super::Solver;
+
+/// Searches every assignment of employees to shifts, so a schedule is found
+/// whenever one exists.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BacktrackingSolver;
+
+/// A shift on one of the operational days and the employees assigned to it.
+struct Slot<'a> {
+    day: usize,
+    shift: &'a Shifts,
+    needed: usize,
+    max: Option<usize>,
+    skills: &'a [SkillRequirement],
+    assigned: Vec<usize>,
+
+    /// Number of pinned employees at the start of `assigned`.
+    pinned: usize,
+}
+
+/// Depth first search over employee assignments.
+///
+/// Employees are added to a slot in increasing roster order so every set of
+/// employees is only tried once, and the slot with the fewest spare candidates
+/// is always filled next. Pinned employees are assigned before searching. Once every slot is filled, employees short of their
+/// contracted hours are added to slots with room to spare. Gives up after trying
+/// the config's `search_limit` assignments.
+struct Search<'a> {
+    employees: &'a [Employee],
+    config: &'a ScheduleConfig,
+    slots: Vec<Slot<'a>>,
+    working: Vec<Vec<Option<&'a Shifts>>>,
+    days_used: Vec<usize>,
+    minutes_used: Vec<u32>,
+    history_days: Vec<usize>,
+
+    /// Shifts each employee worked in each undesirable group in the counted past weeks.
+    past_undesirable: Vec<Vec<usize>>,
+    carried: Vec<Option<&'a Shifts>>,
+    previous_day: Vec<Option<usize>>,
+    next_day: Vec<Option<usize>>,
+
+    /// Whether each employee may work each set of operational days, as a bit mask,
+    /// under the consecutive days rules.
+    patterns: Vec<Vec<bool>>,
+
+    /// Assignments tried so far.
+    tried: usize,
+}
+
+impl<'a> Search<'a> {
+
+    fn new(employees: &'a [Employee], config: &'a ScheduleConfig) -> Self {
+        let slots = config.operational_days.iter().enumerate()
+            .flat_map(|(day, &d)| config.shifts.iter().map(move |shift| {
+                let demand = config.demand(d, shift);
+                Slot {
+                    day,
+                    shift,
+                    needed: demand.min,
+                    max: demand.max,
+                    skills: config.required_skills(d, shift),
+                    assigned: Vec::new(),
+                    pinned: 0,
+                }
+            }))
+            .collect();
+
+        // Operational days directly before and after each day, for the rest rules
+        let index_of = |day: Option<Days>| day.and_then(|d| config.operational_days.iter().position(|&o| o == d));
+        let previous_day = config.operational_days.iter().map(|&d| index_of(config.previous_day(d))).collect();
+        let next_day = config.operational_days.iter().map(|&d| index_of(config.next_day(d))).collect();
+
+        let masks = 1 << config.operational_days.len();
+        let patterns = employees.iter()
+            .map(|employee| (0..masks).map(|mask: usize| {
+                let days: Vec<Days> = config.operational_days.iter().enumerate()
+                    .filter(|&(d, _)| mask & (1 << d) != 0)
+                    .map(|(_, &d)| d)
+                    .collect();
+                config.allows_work_days_for(employee, &days)
+            }).collect())
+            .collect();
+
+        let mut search = Self {
+            employees,
+            config,
+            slots,
+            working: vec![vec![None; config.operational_days.len()]; employees.len()],
+            days_used: vec![0; employees.len()],
+            minutes_used: vec![0; employees.len()],
+            history_days: employees.iter().map(|e| config.history_days(e)).collect(),
+            past_undesirable: employees.iter()
+                .map(|e| config.undesirable.iter().map(|group| config.undesirable_worked(e, group, &Schedule::new())).collect())
+                .collect(),
+            carried: employees.iter().map(|e| config.carried_shift(e)).collect(),
+            previous_day,
+            next_day,
+            patterns,
+            tried: 0,
+        };
+
+        for slot in 0..search.slots.len() {
+            let day = config.operational_days[search.slots[slot].day];
+            for (emp, employee) in employees.iter().enumerate() {
+                if config.is_pinned(employee, day, search.slots[slot].shift) {
+                    search.assign(slot, emp);
+                    search.slots[slot].pinned += 1;
+                }
+            }
+        }
+        search
+    }
+
+    /// Employees with `required` skill still missing from a slot.
+    fn skill_missing(&self, slot: &Slot, required: &SkillRequirement) -> usize {
+        let skilled = slot.assigned.iter().filter(|&&emp| self.employees[emp].has_skill(&required.skill)).count();
+        required.count.saturating_sub(skilled)
+    }
+
+    /// Fewest employees still to add to a slot to meet its staffing and skills.
+    fn remaining(&self, slot: &Slot) -> usize {
+        slot.skills.iter()
+            .map(|required| self.skill_missing(slot, required))
+            .fold(slot.needed.saturating_sub(slot.assigned.len()), usize::max)
+    }
+
+    fn can_work(&self, emp: usize, day: usize) -> bool {
+        self.working[emp][day].is_none() && self.days_used[emp] < self.config.emp_max_days
+    }
+
+    /// Checks the shifts worked on the surrounding days leave enough rest.
+    fn rested(&self, emp: usize, day: usize, shift: &Shifts) -> bool {
+        let before = match self.previous_day[day] {
+            Some(d) => self.working[emp][d],
+            None if self.config.operational_days[day] == Days::Monday => self.carried[emp],
+            None => None,
+        };
+        let after = self.next_day[day].and_then(|d| self.working[emp][d]);
+
+        before.is_none_or(|previous| self.config.rest_allows(previous, shift))
+            && after.is_none_or(|next| self.config.rest_allows(shift, next))
+    }
+
+    /// Operational days an employee works, as a bit mask.
+    fn working_days(&self, emp: usize) -> usize {
+        self.working[emp].iter().enumerate()
+            .filter(|(_, shift)| shift.is_some())
+            .fold(0, |mask, (day, _)| mask | (1 << day))
+    }
+
+    /// Checks adding a day keeps the employee within the consecutive days rules.
+    fn pattern_allows(&self, emp: usize, day: usize) -> bool {
+        self.patterns[emp][self.working_days(emp) | (1 << day)]
+    }
+
+    /// Most of the `open` days, as a bit mask, the employee can add without breaking
+    /// the consecutive days rules.
+    fn pattern_capacity(&self, emp: usize, open: usize) -> usize {
+        let working = self.working_days(emp);
+        // Every subset of the open days, from all of them down to none
+        let mut best = 0;
+        let mut days = open;
+        loop {
+            if days.count_ones() as usize > best && self.patterns[emp][working | days] {
+                best = days.count_ones() as usize;
+            }
+            if days == 0 {
+                break;
+            }
+            days = (days - 1) & open;
+        }
+        best
+    }
+
+    fn can_work_slot(&self, emp: usize, slot: &Slot) -> bool {
+        self.can_work(emp, slot.day)
+            && self.config.can_assign(&self.employees[emp], self.config.operational_days[slot.day], slot.shift)
+            && self.rested(emp, slot.day, slot.shift)
+            && self.pattern_allows(emp, slot.day)
+            && self.employees[emp].contract.allows(self.minutes_used[emp] + slot.shift.duration_minutes())
+    }
+
+    fn candidates<'b>(&'b self, slot: &'b Slot) -> impl Iterator<Item = usize> + 'b {
+        let after = slot.assigned[slot.pinned..].last().map_or(0, |&last| last + 1);
+        (after..self.employees.len()).filter(move |&emp| self.can_work_slot(emp, slot))
+    }
+
+    /// Checks an employee can work one of the shifts still open on a day.
+    fn can_cover(&self, emp: usize, day: usize) -> bool {
+        self.slots.iter().any(|slot| slot.day == day && self.remaining(slot) > 0 && self.can_work_slot(emp, slot))
+    }
+
+    /// Checks the remaining demand can still be covered by the employees left.
+    fn feasible(&self) -> bool {
+        let num_days = self.config.operational_days.len();
+        let mut day_remaining = vec![0; num_days];
+
+        for slot in &self.slots {
+            let remaining = self.remaining(slot);
+            if remaining > 0 && self.candidates(slot).take(remaining).count() < remaining {
+                return false;
+            }
+            if slot.max.is_some_and(|max| slot.assigned.len() + remaining > max) {
+                return false;
+            }
+            for required in slot.skills {
+                let missing = self.skill_missing(slot, required);
+                if missing > 0 && self.candidates(slot).filter(|&emp| self.employees[emp].has_skill(&required.skill)).take(missing).count() < missing {
+                    return false;
+                }
+            }
+            day_remaining[slot.day] += remaining;
+        }
+
+        for (day, &remaining) in day_remaining.iter().enumerate() {
+            let free = (0..self.employees.len()).filter(|&emp| self.can_cover(emp, day)).count();
+            if free < remaining {
+                return false;
+            }
+        }
+
+        let capacity: usize = (0..self.employees.len()).map(|emp| {
+            let open_days = (0..num_days)
+                .filter(|&day| day_remaining[day] > 0 && self.can_cover(emp, day))
+                .fold(0, |mask, day| mask | (1 << day));
+            self.pattern_capacity(emp, open_days).min(self.config.emp_max_days.saturating_sub(self.days_used[emp]))
+        }).sum();
+
+        if capacity < day_remaining.iter().sum() {
+            return false;
+        }
+
+        // Every employee must still be able to reach their contracted hours
+        let longest = self.config.shifts.iter().map(|shift| shift.duration_minutes()).max().unwrap_or(0);
+        (0..self.employees.len()).all(|emp| {
+            let free_days = (0..num_days).filter(|&day| self.working[emp][day].is_none()).count()
+                .min(self.config.emp_max_days.saturating_sub(self.days_used[emp]));
+            self.employees[emp].contract.is_met(self.minutes_used[emp] + free_days as u32 * longest)
+        })
+    }
+
+    /// Shifts an employee works in the undesirable groups a slot is in, including past weeks.
+    fn undesirable_worked(&self, emp: usize, slot: &Slot) -> usize {
+        let covers = |group: &Undesirable, slot: &Slot| group.covers(self.config.operational_days[slot.day], slot.shift);
+        self.config.undesirable.iter().enumerate()
+            .filter(|(_, group)| covers(group, slot))
+            .map(|(index, group)| {
+                let worked = self.slots.iter().filter(|s| covers(group, s) && s.assigned.contains(&emp)).count();
+                self.past_undesirable[emp][index] + worked
+            })
+            .sum()
+    }
+
+    fn next_slot(&self) -> Option<usize> {
+        self.slots.iter().enumerate()
+            .filter(|(_, slot)| self.remaining(slot) > 0)
+            .min_by_key(|(_, slot)| self.candidates(slot).count() - self.remaining(slot))
+            .map(|(index, _)| index)
+    }
+
+    fn assign(&mut self, slot: usize, emp: usize) {
+        let day = self.slots[slot].day;
+        self.slots[slot].assigned.push(emp);
+        self.working[emp][day] = Some(self.slots[slot].shift);
+        self.days_used[emp] += 1;
+        self.minutes_used[emp] += self.slots[slot].shift.duration_minutes();
+    }
+
+    fn unassign(&mut self, slot: usize) {
+        let day = self.slots[slot].day;
+        if let Some(emp) = self.slots[slot].assigned.pop() {
+            self.working[emp][day] = None;
+            self.days_used[emp] -= 1;
+            self.minutes_used[emp] -= self.slots[slot].shift.duration_minutes();
+        }
+    }
+
+    /// Checks the search has tried as many assignments as the config allows.
+    fn gave_up(&self) -> bool {
+        self.config.search_limit.is_some_and(|limit| self.tried >= limit)
+    }
+
+    fn search(&mut self) -> bool {
+        if !self.feasible() {
+            return false;
+        }
+
+        let Some(slot) = self.next_slot() else {
+            return self.top_up();
+        };
+
+        // Try employees with a missing skill first, then those ranking the shift highest,
+        // then those with the fewest undesirable shifts, then the least scheduled
+        let current = &self.slots[slot];
+        let mut candidates: Vec<_> = self.candidates(current).collect();
+        candidates.sort_by_key(|&emp| {
+            let employee = &self.employees[emp];
+            let adds_skill = current.skills.iter()
+                .any(|required| employee.has_skill(&required.skill) && self.skill_missing(current, required) > 0);
+            let days = self.days_used[emp] + self.history_days[emp];
+            (!adds_skill, employee.preference_rank(current.shift).unwrap_or(usize::MAX), self.undesirable_worked(emp, current), days)
+        });
+
+        for emp in candidates {
+            if self.gave_up() {
+                return false;
+            }
+            self.tried += 1;
+            self.assign(slot, emp);
+            if self.search() {
+                return true;
+            }
+            self.unassign(slot);
+        }
+
+        false
+    }
+
+    /// Gives employees below their contracted hours extra shifts, searching
+    /// every slot with room for them.
+    fn top_up(&mut self) -> bool {
+        let Some(emp) = (0..self.employees.len())
+            .find(|&emp| !self.employees[emp].contract.is_met(self.minutes_used[emp])) else {
+            return true;
+        };
+
+        let employee = &self.employees[emp];
+        let mut slots: Vec<_> = (0..self.slots.len())
+            .filter(|&slot| {
+                let slot = &self.slots[slot];
+                slot.max.is_none_or(|max| slot.assigned.len() < max) && self.can_work_slot(emp, slot)
+            })
+            .collect();
+        slots.sort_by_key(|&slot| employee.preference_rank(self.slots[slot].shift).unwrap_or(usize::MAX));
+
+        for slot in slots {
+            if self.gave_up() {
+                return false;
+            }
+            self.tried += 1;
+            self.assign(slot, emp);
+            if self.top_up() {
+                return true;
+            }
+            self.unassign(slot);
+        }
+
+        false
+    }
+}
+
+impl Solver for BacktrackingSolver {
+
+    fn name(&self) -> &str {
+        "backtracking"
+    }
+
+    /// Finds a schedule meeting every rule, or proves that none exists within the
+    /// config's `search_limit`.
+    fn solve(&self, employees: &[Employee], config: &ScheduleConfig) -> Result<Schedule, SchedulerError> {
+        config.check_demand()?;
+        let mut search = Search::new(employees, config);
+
+        if !search.search() {
+            return Err(if search.gave_up() {
+                SchedulerError::SearchLimitReached { tried: search.tried }
+            } else {
+                SchedulerError::Infeasible
+            });
+        }
+
+        let mut schedule = Schedule::new();
+        for slot in &search.slots {
+            let day = config.operational_days[slot.day];
+            schedule.insert(day, slot.shift.clone(), slot.assigned.iter().map(|&emp| employees[emp].clone()).collect());
+        }
+
+        Ok(schedule)
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::{Days, Employee, Schedule, ScheduleConfig, SchedulerError, Shifts};
+
+use super::Solver;
+
+/// Fills the week day by day, giving each shift to the employees who rank it
+/// highest and then to those with the fewest undesirable shifts and the least scheduled.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GreedySolver;
+
+impl Solver for GreedySolver {
+
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn solve(&self, employees: &[Employee], config: &ScheduleConfig) -> Result<Schedule, SchedulerError> {
+        config.check_demand()?;
+
+        let mut schedule = Schedule::new();
+        let mut emp_days = HashMap::<Employee, usize>::new();
+        let mut emp_minutes = HashMap::<Employee, u32>::new();
+        let mut emp_work_days: HashMap<Employee, Vec<Days>> = employees.iter().map(|e| (e.clone(), Vec::new())).collect();
+
+        // Days held back for pinned shifts later in the week
+        let mut pinned_days = HashMap::<Employee, usize>::new();
+        for &day in &config.operational_days {
+            for shift in &config.shifts {
+                for e in config.pinned_employees(employees, day, shift) {
+                    *pinned_days.entry(e.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        for &day in &config.operational_days {
+            let mut day_employees: Vec<Employee> = Vec::new();
+
+            // Employees pinned today only work their pinned shift
+            let pinned_today: Vec<&Employee> = config.shifts.iter()
+                .flat_map(|shift| config.pinned_employees(employees, day, shift))
+                .collect();
+            for e in &pinned_today {
+                if let Some(days) = pinned_days.get_mut(*e) {
+                    *days -= 1;
+                }
+            }
+
+            // Shifts each employee works or is pinned to the days before and after, for the rest rules
+            let shifts_on = |other: Option<Days>| {
+                let mut shifts = HashMap::<Employee, &Shifts>::new();
+                for s in &config.shifts {
+                    for e in other.map_or(&[][..], |d| schedule.assignments(d, s)) {
+                        shifts.insert(e.clone(), s);
+                    }
+                    for e in other.map_or(Vec::new(), |d| config.pinned_employees(employees, d, s)) {
+                        shifts.insert(e.clone(), s);
+                    }
+                }
+                shifts
+            };
+            let mut previous_shifts = shifts_on(config.previous_day(day));
+            if day == Days::Monday {
+                for e in employees {
+                    if let Some(shift) = config.carried_shift(e) {
+                        previous_shifts.insert(e.clone(), shift);
+                    }
+                }
+            }
+            let next_shifts = shifts_on(config.next_day(day));
+            let rules_allow = |e: &Employee, shift: &Shifts| {
+                previous_shifts.get(e).is_none_or(|previous| config.rest_allows(previous, shift))
+                    && next_shifts.get(e).is_none_or(|next| config.rest_allows(shift, next))
+                    && config.allows_work_days_for(e, &[&emp_work_days[e][..], &[day]].concat())
+            };
+
+            for shift in &config.shifts {
+                let needed = config.demand(day, shift).min;
+                // Days worked this week and in the history, for the least scheduled ordering
+                let days_worked = |e: &Employee| emp_days.get(e).unwrap_or(&0) + config.history_days(e);
+                // Shifts worked in the undesirable groups this shift is in, to share them out
+                let undesirable_worked = |e: &Employee| config.undesirable.iter()
+                    .filter(|group| group.covers(day, shift))
+                    .map(|group| config.undesirable_worked(e, group, &schedule))
+                    .sum::<usize>();
+                let hours_allow = |e: &Employee| e.contract.allows(emp_minutes.get(e).unwrap_or(&0) + shift.duration_minutes());
+                let days_left = |e: &Employee| {
+                    emp_days.get(e).unwrap_or(&0) + pinned_days.get(e).unwrap_or(&0) < config.emp_max_days && !pinned_today.contains(&e)
+                };
+
+                // Start from the pinned employees, then cover the required skills, preferring
+                // employees who ranked the shift
+                let mut shift_employees: Vec<Employee> = config.pinned_employees(employees, day, shift).into_iter().cloned().collect();
+                for required in config.required_skills(day, shift) {
+                    let have = shift_employees.iter().filter(|e| e.has_skill(&required.skill)).count();
+                    let mut skilled: Vec<_> = employees.iter().filter(|e| {
+                        days_left(e) && !shift_employees.contains(e) && !day_employees.contains(e)
+                            && e.has_skill(&required.skill) && config.can_assign(e, day, shift) && rules_allow(e, shift) && hours_allow(e)
+                    }).collect();
+                    skilled.sort_by_key(|e| (e.preference_rank(shift).unwrap_or(usize::MAX), undesirable_worked(e), days_worked(e)));
+
+                    if have + skilled.len() < required.count {
+                        return Err(SchedulerError::MissingSkill {
+                            day,
+                            shift: shift.clone(),
+                            skill: required.skill.clone(),
+                            needed: required.count,
+                            found: have + skilled.len(),
+                        });
+                    }
+                    shift_employees.extend(skilled.into_iter().take(required.count.saturating_sub(have)).cloned().collect::<Vec<_>>());
+                }
+
+                let mut available_employees: Vec<_> = employees.iter().filter(|e| {
+                   days_left(e) && !shift_employees.contains(e) && !day_employees.contains(e) && e.preference_rank(shift).is_some() && config.can_assign(e, day, shift)
+                       && rules_allow(e, shift) && hours_allow(e)
+                }).collect();
+
+                // Sort the list by preference, then by the fewest undesirable shifts and the least scheduled employees
+                available_employees.sort_by_key(|e| (e.preference_rank(shift), undesirable_worked(e), days_worked(e)));
+                
+                let places = needed.saturating_sub(shift_employees.len());
+                shift_employees.extend(available_employees.into_iter().take(places).cloned().collect::<Vec<_>>());
+                if shift_employees.len() < needed {
+                    let mut available_employees: Vec<_> = employees.iter().filter(|e| {
+                        days_left(e) && !shift_employees.contains(e) && !day_employees.contains(e)
+                            && config.can_assign(e, day, shift) && rules_allow(e, shift) && hours_allow(e)
+                    }).collect();
+
+                    // Sort the list by the fewest undesirable shifts, then the least scheduled employees
+                    available_employees.sort_by_key(|e| (undesirable_worked(e), days_worked(e)));
+                    
+                    if available_employees.len() + shift_employees.len() < needed {
+                        let found = available_employees.len() + shift_employees.len();
+
+                        // Employees who could have worked the shift if not for time off
+                        let unavailable: Vec<_> = employees.iter().filter(|e| {
+                            days_left(e) && !day_employees.contains(e)
+                                && e.can_work(shift) && !config.can_assign(e, day, shift) && rules_allow(e, shift) && hours_allow(e)
+                        }).map(|e| e.name.clone()).collect();
+
+                        if !unavailable.is_empty() {
+                            return Err(SchedulerError::TimeOffConflict {
+                                day,
+                                shift: shift.clone(),
+                                needed,
+                                found,
+                                unavailable,
+                            });
+                        }
+
+                        return Err(SchedulerError::ShiftUnderstaffed {
+                            day,
+                            shift: shift.clone(),
+                            needed,
+                            found,
+                        });
+                    }
+
+                    shift_employees.extend(available_employees.into_iter()
+                        .take(needed - shift_employees.len()).cloned().collect::<Vec<_>>());
+                }
+                for e in &shift_employees {
+                    *emp_days.entry(e.clone()).or_insert(0) += 1;
+                    *emp_minutes.entry(e.clone()).or_insert(0) += shift.duration_minutes();
+                    day_employees.push(e.clone());
+                }
+                schedule.insert(day, shift.clone(), shift_employees);
+            }
+            for e in &day_employees {
+                emp_work_days.entry(e.clone()).or_default().push(day);
+            }
+        }
+
+        top_up(employees, config, &mut schedule)?;
+
+        Ok(schedule)
+    }
+}
+
+/// Adds employees short of their contracted hours to shifts with room to spare.
+fn top_up(employees: &[Employee], config: &ScheduleConfig, schedule: &mut Schedule) -> Result<(), SchedulerError> {
+    for emp in employees.iter().filter(|e| e.contract.min_hours > 0) {
+        for &day in &config.operational_days {
+            let minutes = schedule.minutes_for(emp);
+            if emp.contract.is_met(minutes) {
+                break;
+            }
+
+            let worked = schedule.shifts_for(emp);
+            let days: Vec<_> = worked.iter().map(|&(d, _)| d).collect();
+            if days.contains(&day) || days.len() >= config.emp_max_days
+                || !config.allows_work_days_for(emp, &[&days[..], &[day]].concat()) {
+                continue;
+            }
+
+            let shift_on = |other: Option<Days>| worked.iter().find(|&&(d, _)| Some(d) == other).map(|(_, s)| s);
+            let previous = shift_on(config.previous_day(day))
+                .or(if day == Days::Monday { config.carried_shift(emp) } else { None });
+            let next = shift_on(config.next_day(day));
+
+            let shift = config.shifts.iter()
+                .filter(|shift| {
+                    config.can_assign(emp, day, shift)
+                        && config.demand(day, shift).allows(schedule.assignments(day, shift).len() + 1)
+                        && emp.contract.allows(minutes + shift.duration_minutes())
+                        && previous.is_none_or(|previous| config.rest_allows(previous, shift))
+                        && next.is_none_or(|next| config.rest_allows(shift, next))
+                })
+                .min_by_key(|shift| emp.preference_rank(shift).unwrap_or(usize::MAX));
+
+            if let Some(shift) = shift {
+                let mut assigned = schedule.assignments(day, shift).to_vec();
+                assigned.push(emp.clone());
+                schedule.insert(day, shift.clone(), assigned);
+            }
+        }
+
+        let scheduled_minutes = schedule.minutes_for(emp);
+        if !emp.contract.is_met(scheduled_minutes) {
+            return Err(SchedulerError::ContractHoursUnmet {
+                employee: emp.name.clone(),
+                min_hours: emp.contract.min_hours,
+                scheduled_minutes,
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use std::fmt;
+
+use crate::{Contract, Days, Employee, Schedule, ScheduleConfig, Shifts};
+
+/// A scheduling rule broken by a schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// Fewer employees than needed on a shift.
+    Understaffed {
+        day: Days,
+        shift: Shifts,
+        needed: usize,
+        found: usize,
+    },
+
+    /// More employees than allowed on a shift.
+    Overstaffed {
+        day: Days,
+        shift: Shifts,
+        max: usize,
+        found: usize,
+    },
+
+    /// Employees on a shift that is not worked, or on a day that is not operational.
+    NotOperational {
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// A scheduled employee who is not on the roster.
+    UnknownEmployee {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// An employee working more than one shift on a day.
+    MultipleShifts {
+        employee: String,
+        day: Days,
+    },
+
+    /// An employee working more days than `emp_max_days`.
+    TooManyDays {
+        employee: String,
+        days: usize,
+        max: usize,
+    },
+
+    /// An employee working a shift they are unavailable, on time off or forbidden for.
+    Unavailable {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// A pinned employee who is not working their shift.
+    PinMissing {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+    },
+
+    /// Not enough employees with a skill on a shift.
+    MissingSkill {
+        day: Days,
+        shift: Shifts,
+        skill: String,
+        needed: usize,
+        found: usize,
+    },
+
+    /// An employee without the required rest between shifts on consecutive days.
+    InsufficientRest {
+        employee: String,
+        day: Days,
+        shift: Shifts,
+        next_shift: Shifts,
+    },
+
+    /// An employee working too many days in a row, or without enough days off in a row.
+    WorkPattern {
+        employee: String,
+    },
+
+    /// An employee scheduled outside their contracted hours.
+    ContractHours {
+        employee: String,
+        contract: Contract,
+        scheduled_minutes: u32,
+    },
+}
+
+impl Violation {
+
+    /// Day the violation is on, `None` when it covers the whole week.
+    pub fn day(&self) -> Option<Days> {
+        match self {
+            Self::Understaffed { day, .. } | Self::Overstaffed { day, .. } | Self::NotOperational { day, .. }
+            | Self::UnknownEmployee { day, .. } | Self::MultipleShifts { day, .. } | Self::Unavailable { day, .. }
+            | Self::PinMissing { day, .. } | Self::MissingSkill { day, .. } | Self::InsufficientRest { day, .. } => Some(*day),
+            Self::TooManyDays { .. } | Self::WorkPattern { .. } | Self::ContractHours { .. } => None,
+        }
+    }
+
+    /// Shift the violation is on, `None` when it covers a whole day or week.
+    pub fn shift(&self) -> Option<&Shifts> {
+        match self {
+            Self::Understaffed { shift, .. } | Self::Overstaffed { shift, .. } | Self::NotOperational { shift, .. }
+            | Self::UnknownEmployee { shift, .. } | Self::Unavailable { shift, .. } | Self::PinMissing { shift, .. }
+            | Self::MissingSkill { shift, .. } | Self::InsufficientRest { shift, .. } => Some(shift),
+            Self::MultipleShifts { .. } | Self::TooManyDays { .. } | Self::WorkPattern { .. } | Self::ContractHours { .. } => None,
+        }
+    }
+
+    /// Name of the employee breaking the rule, `None` for staffing violations.
+    pub fn employee(&self) -> Option<&str> {
+        match self {
+            Self::UnknownEmployee { employee, .. } | Self::MultipleShifts { employee, .. } | Self::TooManyDays { employee, .. }
+            | Self::Unavailable { employee, .. } | Self::PinMissing { employee, .. } | Self::InsufficientRest { employee, .. }
+            | Self::WorkPattern { employee } | Self::ContractHours { employee, .. } => Some(employee),
+            Self::Understaffed { .. } | Self::Overstaffed { .. } | Self::NotOperational { .. } | Self::MissingSkill { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Understaffed { day, shift, needed, found } => {
+                write!(f, "{} {} needs {} employees, {} scheduled", day, shift, needed, found)
+            },
+            Self::Overstaffed { day, shift, max, found } => {
+                write!(f, "{} {} allows {} employees, {} scheduled", day, shift, max, found)
+            },
+            Self::NotOperational { day, shift } => {
+                write!(f, "{} {} is not worked but has employees scheduled", day, shift)
+            },
+            Self::UnknownEmployee { employee, day, shift } => {
+                write!(f, "{} is scheduled on {} {} but is not an employee", employee, day, shift)
+            },
+            Self::MultipleShifts { employee, day } => {
+                write!(f, "{} works more than one shift on {}", employee, day)
+            },
+            Self::TooManyDays { employee, days, max } => {
+                write!(f, "{} works {} days, more than {}", employee, days, max)
+            },
+            Self::Unavailable { employee, day, shift } => {
+                write!(f, "{} cannot work {} {}", employee, day, shift)
+            },
+            Self::PinMissing { employee, day, shift } => {
+                write!(f, "{} is pinned to {} {} but not scheduled", employee, day, shift)
+            },
+            Self::MissingSkill { day, shift, skill, needed, found } => {
+                write!(f, "{} {} needs {} employees with skill {}, {} scheduled", day, shift, needed, skill, found)
+            },
+            Self::InsufficientRest { employee, day, shift, next_shift } => {
+                write!(f, "{} works {} {} without enough rest before the next day's {}", employee, day, shift, next_shift)
+            },
+            Self::WorkPattern { employee } => {
+                write!(f, "{} breaks the consecutive days or days off rules", employee)
+            },
+            Self::ContractHours { employee, contract, scheduled_minutes } => {
+                write!(f, "{} is scheduled {:.1} hours, outside their contract of {}", employee, *scheduled_minutes as f32 / 60.0, contract)
+            },
+        }
+    }
+}
+
+/// Checks a schedule against every rule in `config`, matching scheduled employees to
+/// the roster by name. Dated pins and forbidden shifts are checked by
+/// `Scheduler::validate_roster`, which knows the week of each schedule.
+///
+/// Useful for schedules that were edited by hand or imported, as solvers only
+/// produce schedules that already follow the rules.
+pub fn validate(schedule: &Schedule, employees: &[Employee], config: &ScheduleConfig) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let find = |name: &str| employees.iter().find(|e| e.name == name);
+
+    // Staffing, skills and pins of each shift
+    for &day in &config.operational_days {
+        for shift in &config.shifts {
+            let assigned = schedule.assignments(day, shift);
+            let demand = config.demand(day, shift);
+            if assigned.len() < demand.min {
+                violations.push(Violation::Understaffed { day, shift: shift.clone(), needed: demand.min, found: assigned.len() });
+            }
+            if let Some(max) = demand.max.filter(|&max| assigned.len() > max) {
+                violations.push(Violation::Overstaffed { day, shift: shift.clone(), max, found: assigned.len() });
+            }
+
+            for required in config.required_skills(day, shift) {
+                let found = assigned.iter()
+                    .filter(|e| find(&e.name).is_some_and(|e| e.has_skill(&required.skill)))
+                    .count();
+                if found < required.count {
+                    violations.push(Violation::MissingSkill {
+                        day,
+                        shift: shift.clone(),
+                        skill: required.skill.clone(),
+                        needed: required.count,
+                        found,
+                    });
+                }
+            }
+
+            for pin in config.weekly_pins().filter(|pin| pin.day == day && &pin.shift == shift) {
+                if !assigned.iter().any(|e| e.name == pin.employee) {
+                    violations.push(Violation::PinMissing { employee: pin.employee.clone(), day, shift: shift.clone() });
+                }
+            }
+        }
+    }
+
+    // Who is scheduled on each shift
+    for (day, shift, assigned) in schedule.iter() {
+        if assigned.is_empty() {
+            continue;
+        }
+        if !config.operational_days.contains(&day) || !config.shifts.contains(shift) {
+            violations.push(Violation::NotOperational { day, shift: shift.clone() });
+        }
+
+        for scheduled in assigned {
+            match find(&scheduled.name) {
+                None => violations.push(Violation::UnknownEmployee { employee: scheduled.name.clone(), day, shift: shift.clone() }),
+                Some(employee) if !config.can_assign(employee, day, shift) => {
+                    violations.push(Violation::Unavailable { employee: employee.name.clone(), day, shift: shift.clone() });
+                },
+                Some(_) => {},
+            }
+        }
+    }
+
+    // Days, patterns and hours worked by each employee
+    for employee in employees {
+        let worked: Vec<(Days, &Shifts)> = schedule.iter()
+            .filter(|(_, _, assigned)| assigned.iter().any(|e| e.name == employee.name))
+            .map(|(day, shift, _)| (day, shift))
+            .collect();
+        let mut days: Vec<Days> = worked.iter().map(|&(day, _)| day).collect();
+        days.dedup();
+
+        for &day in &days {
+            if worked.iter().filter(|&&(d, _)| d == day).count() > 1 {
+                violations.push(Violation::MultipleShifts { employee: employee.name.clone(), day });
+            }
+        }
+        if days.len() > config.emp_max_days {
+            violations.push(Violation::TooManyDays { employee: employee.name.clone(), days: days.len(), max: config.emp_max_days });
+        }
+        if !config.allows_work_days_for(employee, &days) {
+            violations.push(Violation::WorkPattern { employee: employee.name.clone() });
+        }
+
+        let scheduled_minutes = worked.iter().map(|(_, shift)| shift.duration_minutes()).sum();
+        if !employee.contract.allows(scheduled_minutes) || !employee.contract.is_met(scheduled_minutes) {
+            violations.push(Violation::ContractHours { employee: employee.name.clone(), contract: employee.contract, scheduled_minutes });
+        }
+    }
+
+    for rest in schedule.rest_violations(config) {
+        violations.push(Violation::InsufficientRest {
+            employee: rest.employee,
+            day: rest.day,
+            shift: rest.shift,
+            next_shift: rest.next_shift,
+        });
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Assignment, Date, RestRule, SkillRequirement, StaffingDemand};
+    use crate::tests::employee;
+
+    #[test]
+    fn test_validate() {
+        let alice = Employee { skills: vec!["keyholder".into()], ..employee("Alice") };
+        let bob = Employee { unavailable_days: vec![Days::Tuesday], ..employee("Bob") };
+        let employees = vec![alice.clone(), bob.clone()];
+
+        let mut demand = std::collections::HashMap::new();
+        demand.insert((Days::Monday, Shifts::morning()), StaffingDemand { min: 1, max: Some(1) });
+        let config = ScheduleConfig {
+            operational_days: vec![Days::Monday, Days::Tuesday],
+            shifts: vec![Shifts::morning(), Shifts::evening()],
+            shift_min_emps: 1,
+            demand,
+            emp_max_days: 1,
+            rest_rules: vec![RestRule::no_morning_after_evening()],
+            shift_skills: vec![SkillRequirement::new("keyholder", 1)],
+            pinned: vec![Assignment::new("Alice", Days::Tuesday, Shifts::evening())],
+            ..Default::default()
+        };
+
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, Shifts::morning(), vec![alice.clone(), bob.clone()]);
+        schedule.insert(Days::Monday, Shifts::evening(), vec![alice.clone()]);
+        schedule.insert(Days::Tuesday, Shifts::morning(), vec![alice.clone(), bob.clone(), employee("Carol")]);
+        schedule.insert(Days::Wednesday, Shifts::morning(), vec![bob.clone()]);
+
+        let violations = validate(&schedule, &employees, &config);
+        let expected = vec![
+            Violation::Overstaffed { day: Days::Monday, shift: Shifts::morning(), max: 1, found: 2 },
+            Violation::Understaffed { day: Days::Tuesday, shift: Shifts::evening(), needed: 1, found: 0 },
+            Violation::MissingSkill { day: Days::Tuesday, shift: Shifts::evening(), skill: "keyholder".into(), needed: 1, found: 0 },
+            Violation::PinMissing { employee: "Alice".into(), day: Days::Tuesday, shift: Shifts::evening() },
+            Violation::Unavailable { employee: "Bob".into(), day: Days::Tuesday, shift: Shifts::morning() },
+            Violation::UnknownEmployee { employee: "Carol".into(), day: Days::Tuesday, shift: Shifts::morning() },
+            Violation::NotOperational { day: Days::Wednesday, shift: Shifts::morning() },
+            Violation::MultipleShifts { employee: "Alice".into(), day: Days::Monday },
+            Violation::TooManyDays { employee: "Alice".into(), days: 2, max: 1 },
+            Violation::TooManyDays { employee: "Bob".into(), days: 3, max: 1 },
+            Violation::InsufficientRest {
+                employee: "Alice".into(),
+                day: Days::Monday,
+                shift: Shifts::evening(),
+                next_shift: Shifts::morning(),
+            },
+        ];
+        assert_eq!(violations, expected);
+        assert_eq!(violations[3].day(), Some(Days::Tuesday));
+        assert_eq!(violations[3].employee(), Some("Alice"));
+        assert_eq!(violations[8].shift(), None);
+        assert_eq!(violations[0].to_string(), "Monday Morning allows 1 employees, 2 scheduled");
+
+        // A schedule following the rules
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, Shifts::morning(), vec![alice.clone()]);
+        schedule.insert(Days::Monday, Shifts::evening(), vec![bob.clone()]);
+        let config = ScheduleConfig { operational_days: vec![Days::Monday], shift_skills: Vec::new(), pinned: Vec::new(), ..config };
+        assert!(validate(&schedule, &employees, &config).is_empty());
+
+        // Employees edited since scheduling are matched by name, and dated pins are left
+        // to `Scheduler::validate_roster`
+        let mut schedule = Schedule::new();
+        schedule.insert(Days::Monday, Shifts::evening(), vec![Employee { skills: Vec::new(), ..alice.clone() }]);
+        schedule.insert(Days::Tuesday, Shifts::morning(), vec![alice.clone()]);
+        let monday = Date::parse("2026-11-02").unwrap();
+        let config = ScheduleConfig {
+            operational_days: vec![Days::Monday, Days::Tuesday],
+            shift_min_emps: 0,
+            demand: std::collections::HashMap::new(),
+            emp_max_days: 2,
+            pinned: vec![Assignment::on("Bob", monday, Shifts::morning())],
+            ..config
+        };
+        assert_eq!(validate(&schedule, &employees, &config), vec![Violation::InsufficientRest {
+            employee: "Alice".into(),
+            day: Days::Monday,
+            shift: Shifts::evening(),
+            next_shift: Shifts::morning(),
+        }]);
+    }
+}